@@ -1,6 +1,10 @@
 mod state;
 mod ui;
 mod runner;
+mod search;
+mod worker;
 
 pub use state::*;
-pub use runner::run; 
\ No newline at end of file
+pub use runner::run;
+pub use search::Search;
+pub use worker::CollectorHandle; 
\ No newline at end of file
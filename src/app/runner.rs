@@ -1,13 +1,14 @@
 use color_eyre::Result;
-use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
-use std::time::{Duration, Instant};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use std::time::Duration;
 use ratatui::Terminal;
 
-use crate::models;
-use super::App;
+use crate::models::{self, DataSource, ProcessKey, SignalKind};
+use super::{App, ChartTarget, CollectorHandle, Mode};
 
-pub fn run(mut terminal: Terminal<impl ratatui::backend::Backend>, mut app: App) -> Result<()> {
-    let mut last_update = Instant::now();
+pub fn run(mut terminal: Terminal<impl ratatui::backend::Backend>, mut app: App, source: impl DataSource + Send + 'static) -> Result<()> {
+    // zbieranie danych przenoszę do osobnego wątku
+    let collector = CollectorHandle::spawn(source, app.interval);
     // przetwarzam uprzednio załadowane dane
     app.sort_data();
     app.save_history_data();
@@ -17,21 +18,25 @@ pub fn run(mut terminal: Terminal<impl ratatui::backend::Backend>, mut app: App)
         // rysowanie
         terminal.draw(|frame| app.draw(frame))?;
 
-        // obsługa stopu
-        if !app.stop {
-            if last_update.elapsed() >= Duration::from_millis(models::INTERVAL) {
-                app.update_data();
-                last_update = Instant::now();
-            }
+        // odbieranie najnowszych danych z wątku (obsługa stopu)
+        if !app.stop
+            && let Some(snapshot) = collector.try_latest()
+        {
+            app.update_data(snapshot);
         }
-        // czekanie + obsługa przycisków
+        // czekanie + obsługa przycisków i myszy
         if event::poll(Duration::from_millis(200))? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    // komunikat znika przy kolejnej akcji użytkownika
+                    app.status_message = None;
                     if handle_key_event(&mut app, key.code, key.modifiers.contains(KeyModifiers::SHIFT)) {
                         break;
                     }
+                    collector.set_paused(app.stop);
                 }
+                Event::Mouse(mouse) => handle_mouse_event(&mut app, mouse),
+                _ => {}
             }
         }
     }
@@ -39,9 +44,181 @@ pub fn run(mut terminal: Terminal<impl ratatui::backend::Backend>, mut app: App)
     Ok(())
 }
 
+// liczba wierszy przewijanych jednym ruchem kółka
+const MOUSE_SCROLL_ROWS: isize = 3;
+
+// mysz działa tylko bez otwartych okienek - klik zaznacza / sortuje / wybiera wykres, kółko przewija
+fn handle_mouse_event(app: &mut App, mouse: MouseEvent) {
+    if !matches!(app.mode, Mode::Normal | Mode::Search) {
+        return;
+    }
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            app.status_message = None;
+            app.click(mouse.column, mouse.row);
+        }
+        MouseEventKind::ScrollDown => app.scroll_rows(MOUSE_SCROLL_ROWS),
+        MouseEventKind::ScrollUp => app.scroll_rows(-MOUSE_SCROLL_ROWS),
+        _ => {}
+    }
+}
+
 fn handle_key_event(app: &mut App, key: KeyCode, shift_pressed: bool) -> bool {
+    match app.mode.clone() {
+        Mode::Normal => handle_normal_key(app, key, shift_pressed),
+        Mode::Details(_) => {
+            handle_details_key(app, key);
+            false
+        }
+        Mode::SignalPicker { target, selected, tree } => {
+            handle_signal_picker_key(app, key, target, selected, tree);
+            false
+        }
+        Mode::ConfirmSignal { targets, signal, .. } => {
+            handle_confirm_signal_key(app, key, &targets, signal);
+            false
+        }
+        Mode::Search => {
+            handle_search_key(app, key);
+            false
+        }
+        Mode::FilterPrompt => {
+            handle_filter_key(app, key);
+            false
+        }
+        Mode::ColumnPicker { selected } => {
+            handle_column_picker_key(app, key, selected, shift_pressed);
+            false
+        }
+        Mode::CpuInfo => {
+            handle_cpu_info_key(app, key);
+            false
+        }
+    }
+}
+
+// wybór kolumn - Spacja pokazuje/ukrywa, Shift + strzałki przesuwają, Enter/Esc zamyka i zapisuje
+fn handle_column_picker_key(app: &mut App, key: KeyCode, selected: usize, shift_pressed: bool) {
+    let order = app.column_picker_order();
+    let column = order[selected];
+    let selected = match key {
+        KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => {
+            app.save_columns();
+            app.mode = Mode::Normal;
+            return;
+        }
+        KeyCode::Up if shift_pressed => {
+            app.move_column(column, -1);
+            column_position(app, column)
+        }
+        KeyCode::Down if shift_pressed => {
+            app.move_column(column, 1);
+            column_position(app, column)
+        }
+        KeyCode::Up => (selected + order.len() - 1) % order.len(),
+        KeyCode::Down => (selected + 1) % order.len(),
+        KeyCode::Char(' ') => {
+            app.toggle_column(column);
+            column_position(app, column)
+        }
+        _ => return,
+    };
+    app.mode = Mode::ColumnPicker { selected };
+}
+
+// pozycja kolumny w okienku wyboru (kursor podąża za przesuwaną kolumną)
+fn column_position(app: &App, column: models::ProcessName) -> usize {
+    app.column_picker_order().iter().position(|c| *c == column).unwrap_or(0)
+}
+
+// wpisywanie wyrażenia filtrującego - Enter zatwierdza, Esc anuluje zmiany
+fn handle_filter_key(app: &mut App, key: KeyCode) {
     match key {
+        KeyCode::Enter => app.submit_filter(),
+        KeyCode::Esc => {
+            app.filter_error = None;
+            app.mode = Mode::Normal;
+        }
+        KeyCode::Backspace => {
+            app.filter_input.pop();
+        }
+        KeyCode::Char(c) => app.filter_input.push(c),
+        _ => {}
+    }
+}
+
+// wpisywanie frazy - filtr działa na bieżąco, Enter zatwierdza, Esc czyści
+fn handle_search_key(app: &mut App, key: KeyCode) {
+    match key {
+        KeyCode::Enter => app.mode = Mode::Normal,
+        KeyCode::Esc => {
+            app.search.clear();
+            app.mode = Mode::Normal;
+        }
+        KeyCode::Tab => app.search.toggle_regex(),
+        KeyCode::Backspace => app.search.pop(),
+        KeyCode::Char(c) => app.search.push(c),
+        _ => return,
+    }
+    app.rebuild_rows();
+}
+
+// wybór sygnału - strzałki zmieniają sygnał, t przełącza całe drzewo procesów
+fn handle_signal_picker_key(app: &mut App, key: KeyCode, target: ProcessKey, selected: usize, tree: bool) {
+    let count = SignalKind::ALL.len();
+    app.mode = match key {
+        KeyCode::Esc | KeyCode::Char('q') => Mode::Normal,
+        KeyCode::Down => Mode::SignalPicker { target, selected: (selected + 1) % count, tree },
+        KeyCode::Up => Mode::SignalPicker { target, selected: (selected + count - 1) % count, tree },
+        KeyCode::Char('t') => Mode::SignalPicker { target, selected, tree: !tree },
+        KeyCode::Enter => Mode::ConfirmSignal { targets: app.signal_targets(target, tree), signal: SignalKind::ALL[selected], tree },
+        _ => return,
+    };
+}
+
+// potwierdzenie - y/Enter wysyła sygnał, n/Esc anuluje
+fn handle_confirm_signal_key(app: &mut App, key: KeyCode, targets: &[ProcessKey], signal: SignalKind) {
+    match key {
+        KeyCode::Char('y') | KeyCode::Enter => {
+            app.send_signal(targets, signal);
+            app.mode = Mode::Normal;
+        }
+        KeyCode::Char('n') | KeyCode::Esc | KeyCode::Char('q') => app.mode = Mode::Normal,
+        _ => {}
+    }
+}
+
+// okienko informacji o procesorze - zamykane przez Esc, Enter, q lub ponowne c
+fn handle_cpu_info_key(app: &mut App, key: KeyCode) {
+    if let KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('c') = key {
+        app.mode = Mode::Normal;
+    }
+}
+
+// okienko szczegółów - zamykane przez Esc, Enter lub q
+fn handle_details_key(app: &mut App, key: KeyCode) {
+    if let KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') = key {
+        app.mode = Mode::Normal;
+    }
+}
+
+fn handle_normal_key(app: &mut App, key: KeyCode, shift_pressed: bool) -> bool {
+    match key {
+        // Esc najpierw czyści aktywne wyszukiwanie
+        KeyCode::Esc if app.search.is_active() => {
+            app.search.clear();
+            app.rebuild_rows();
+            false
+        }
         KeyCode::Char('q') | KeyCode::Esc => true,
+        KeyCode::Char('f') => {
+            app.open_filter_prompt();
+            false
+        }
+        KeyCode::Char('/') => {
+            app.mode = Mode::Search;
+            false
+        }
         KeyCode::Down if shift_pressed => {
             app.reverse_sort = true;
             app.sort_data();
@@ -61,12 +238,12 @@ fn handle_key_event(app: &mut App, key: KeyCode, shift_pressed: bool) -> bool {
             false
         }
         KeyCode::Left if shift_pressed => {
-            app.sort_tag = app.sort_tag.prev();
+            app.previous_sort_column();
             app.sort_data();
             false
         }
         KeyCode::Right if shift_pressed => {
-            app.sort_tag = app.sort_tag.next();
+            app.next_sort_column();
             app.sort_data();
             false
         }
@@ -79,19 +256,67 @@ fn handle_key_event(app: &mut App, key: KeyCode, shift_pressed: bool) -> bool {
             false
         }
         KeyCode::Tab => {
-            app.plot_cpu = true;
+            app.chart = ChartTarget::CpuAvg;
             false
         }
         KeyCode::Enter => {
-            if let Some(selected) = app.state.selected() {
-                if let Some(proc) = app.items.get(selected) {
-                    app.chart_col = app.selected_column;
-                    app.chart_pid = proc.pid;
-                    app.plot_cpu = false;
-                }
+            if let Some(key) = app.selected_process().map(|proc| proc.key()) {
+                app.chart_col = app.selected_column_name();
+                app.chart = ChartTarget::Process(key);
+                app.open_details(key.pid);
             }
             false
         }
+        KeyCode::Char('I') => {
+            app.toggle_cpu_mode();
+            false
+        }
+        KeyCode::Char('c') => {
+            app.mode = Mode::CpuInfo;
+            false
+        }
+        KeyCode::Char('m') => {
+            app.chart = ChartTarget::Memory;
+            false
+        }
+        KeyCode::Char('p') => {
+            app.next_pressure_chart();
+            false
+        }
+        KeyCode::Char('b') => {
+            app.next_block_device_chart();
+            false
+        }
+        KeyCode::Char('n') => {
+            app.next_network_chart();
+            false
+        }
+        KeyCode::Char('z') => {
+            app.toggle_freeze_order();
+            false
+        }
+        KeyCode::Char('t') => {
+            app.toggle_tree();
+            false
+        }
+        KeyCode::Char('o') => {
+            app.mode = Mode::ColumnPicker { selected: 0 };
+            false
+        }
+        KeyCode::Char('k') => {
+            if let Some(target) = app.selected_process().map(|proc| proc.key()) {
+                app.mode = Mode::SignalPicker { target, selected: 0, tree: false };
+            }
+            false
+        }
+        KeyCode::Char('+') => {
+            app.set_collapsed(false);
+            false
+        }
+        KeyCode::Char('-') => {
+            app.set_collapsed(true);
+            false
+        }
         KeyCode::Char(' ') => {
             app.stop = !app.stop;
             false
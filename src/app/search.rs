@@ -0,0 +1,79 @@
+use regex::{Regex, RegexBuilder};
+
+use crate::models::ProcessInfo;
+
+// wyszukiwanie procesów po nazwie i linii poleceń (tryb "/")
+#[derive(Default)]
+pub struct Search {
+    pub query: String, // wpisany tekst
+    pub regex: bool, // czy traktować tekst jako wyrażenie regularne
+    pub error: Option<String>, // błąd kompilacji wyrażenia regularnego
+    pattern: Option<Regex>, // skompilowane wyrażenie (tylko w trybie regex)
+}
+
+impl Search {
+    // czy filtr cokolwiek odrzuca
+    pub fn is_active(&self) -> bool {
+        !self.query.is_empty()
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.query.push(c);
+        self.compile();
+    }
+
+    pub fn pop(&mut self) {
+        self.query.pop();
+        self.compile();
+    }
+
+    pub fn clear(&mut self) {
+        self.query.clear();
+        self.compile();
+    }
+
+    pub fn toggle_regex(&mut self) {
+        self.regex = !self.regex;
+        self.compile();
+    }
+
+    // kompilacja wyrażenia po każdej zmianie tekstu
+    fn compile(&mut self) {
+        self.pattern = None;
+        self.error = None;
+        if !self.regex || self.query.is_empty() {
+            return;
+        }
+        match RegexBuilder::new(&self.query).case_insensitive(true).build() {
+            Ok(pattern) => self.pattern = Some(pattern),
+            Err(err) => {
+                // pokazujemy tylko ostatnią linię opisu błędu (bez wskaźnika pozycji)
+                self.error = err.to_string().lines().last().map(|line| line.trim().to_string())
+            }
+        }
+    }
+
+    // czy proces pasuje do wyszukiwania (niepoprawne wyrażenie nie odrzuca niczego)
+    pub fn matches(&self, proc: &ProcessInfo) -> bool {
+        if !self.is_active() || self.error.is_some() {
+            return true;
+        }
+        let command = proc.command_line();
+        match &self.pattern {
+            Some(pattern) => pattern.is_match(&proc.name) || pattern.is_match(&command),
+            None => {
+                let query = self.query.to_lowercase();
+                proc.name.to_lowercase().contains(&query) || command.to_lowercase().contains(&query)
+            }
+        }
+    }
+
+    // opis filtra do tytułu tabeli
+    pub fn describe(&self) -> String {
+        match (&self.error, self.regex) {
+            (Some(err), _) => format!("regex /{}/ invalid: {err}", self.query),
+            (None, true) => format!("regex /{}/", self.query),
+            (None, false) => format!("search \"{}\"", self.query),
+        }
+    }
+}
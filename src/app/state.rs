@@ -1,108 +1,389 @@
-use std::collections::{HashMap, VecDeque};
-use sysinfo::{Pid, System};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use sysinfo::Pid;
 use unicode_width::UnicodeWidthStr;
 use ratatui::widgets::TableState;
 use std::cmp::max;
 use ratatui::widgets::ScrollbarState;
+use ratatui::layout::{Constraint, Layout, Margin, Position, Rect};
 
-use crate::models::{ProcessInfo, ProcessInfoHistory, GeneralInfo, ProcessName, COLUMN_LABEL};
-use crate::utils::push_bounded;
+use crate::models::{is_running, read_cwd, send_signal, Filter, FilterError, SignalKind, Snapshot, ProcessInfo, ProcessInfoHistory, ProcessKey, GeneralInfo, NetworkHistory, BlockDeviceHistory, CoreHistory, CpuBreakdown, MemoryHistory, PressureHistory, PressureResource, ProcessName};
+use crate::utils::{change_units, push_bounded, CpuMode, Units};
+use crate::config::{Config, Panels, Settings};
+use super::Search;
+
+// wiersz tabeli procesów - indeks w `items` i wcięcie w widoku drzewa
+pub struct VisibleRow {
+    pub index: usize,
+    pub prefix: String,
+}
+
+// tryb interfejsu - decyduje, który widok obsługuje klawiaturę
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mode {
+    Normal, // nawigacja po tabeli
+    Details(Pid), // okienko ze szczegółami procesu
+    // wybór sygnału (tree - także potomkowie), proces zapamiętany z czasem startu - PID mógł zostać użyty ponownie
+    SignalPicker { target: ProcessKey, selected: usize, tree: bool },
+    // potwierdzenie wysłania sygnału - cele (proces, potem potomkowie) ustalone przy otwarciu okienka
+    ConfirmSignal { targets: Vec<ProcessKey>, signal: SignalKind, tree: bool },
+    Search, // wpisywanie wyszukiwanej frazy
+    FilterPrompt, // wpisywanie wyrażenia filtrującego
+    ColumnPicker { selected: usize }, // wybór i kolejność widocznych kolumn
+    CpuInfo, // okienko z modelem, częstotliwościami i topologią procesora
+}
+
+// co jest aktualnie rysowane na wykresie
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChartTarget {
+    CpuAvg, // średnie zużycie procesora
+    Process(ProcessKey), // kolumna `chart_col` danego procesu
+    Core(usize), // zużycie jednego rdzenia (indeks w `cpu_usage_tab`)
+    Disk(String), // zajętość dysku o danym punkcie montowania
+    Pressure(PressureResource), // presja (PSI) na zasób - "some" i "full"
+    Memory, // zajętość pamięci, pamięci podręcznej i swapu
+    Network(String), // przepustowość (odbiór i wysyłanie) interfejsu sieciowego
+    BlockDevice { name: String, metric: BlockMetric }, // obciążenie urządzenia blokowego
+}
+
+// wielkość rysowana dla urządzenia blokowego
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockMetric {
+    Throughput, // odczyt i zapis w bajtach na sekundę
+    Iops,
+    Await,
+    Util,
+}
+
+impl BlockMetric {
+    pub const ALL: [BlockMetric; 4] = [BlockMetric::Throughput, BlockMetric::Iops, BlockMetric::Await, BlockMetric::Util];
+}
+
+// szerokości kolumn tabeli urządzeń blokowych (urządzenie, odczyt, zapis, IOPS, await, zajętość)
+pub const BLOCK_DEVICE_TABLE_WIDTHS: [Constraint; 6] = [Constraint::Fill(1); 6];
+
+// szerokość miejsca na znacznik zaznaczenia (">> ") w tabeli procesów
+pub const PROCESS_TABLE_SELECTION_WIDTH: u16 = 3;
+
+// czy `y` to wiersz nagłówka tabeli z ramką
+fn is_table_header(area: Rect, y: u16) -> bool {
+    y == area.y + 1
+}
+
+// indeks wiersza tabeli z ramką i nagłówkiem pod wysokością `y`
+fn table_row_at(area: Rect, offset: usize, y: u16) -> Option<usize> {
+    let inner = area.inner(Margin { vertical: 1, horizontal: 1 });
+    if y <= inner.y || y >= inner.bottom() {
+        return None;
+    }
+    Some(offset + (y - inner.y - 1) as usize)
+}
+
+// indeks kolumny tabeli pod pozycją `x` - ten sam podział co w `Table` z ratatui
+fn table_column_at(area: Rect, widths: &[Constraint], selection_width: u16, x: u16) -> Option<usize> {
+    let inner = area.inner(Margin { vertical: 1, horizontal: 1 });
+    let [_, columns_area] = Layout::horizontal([Constraint::Length(selection_width), Constraint::Fill(0)]).areas(inner);
+    Layout::horizontal(widths.to_vec())
+        .spacing(1)
+        .split(columns_area)
+        .iter()
+        .position(|rect| x >= rect.x && x < rect.right())
+}
+
+// komunikat wyświetlany na dole tabeli procesów
+pub struct StatusMessage {
+    pub text: String,
+    pub is_error: bool,
+}
 
 // struktura stanu aplikacji
 pub struct App {
     pub state: TableState, // stan tabeli
     pub items: Vec<ProcessInfo>, // tablica aktualnie zczytanych danych o procesach
-    pub longest_item_lens: (u16, u16, u16, u16, u16, u16, u16, u16), // szerokość kolumn
+    pub rows: Vec<VisibleRow>, // wiersze faktycznie wyświetlane w tabeli (kolejność + drzewo)
+    pub columns: Vec<ProcessName>, // widoczne kolumny tabeli, w kolejności
+    pub saved_columns: Vec<ProcessName>, // kolumny zapisane w konfiguracji (albo wczytane z niej)
+    pub longest_item_lens: Vec<u16>, // szerokość kolumn (dla każdej z `columns`)
+    pub tree_indent: usize, // najszersze wcięcie drzewa przed nazwą procesu (0 poza widokiem drzewa)
     pub scroll_state: ScrollbarState, // stan scrollbar'a
     pub selected_column: usize, // zaznaczona kolumna przez kursor
     pub sort_tag: ProcessName, // nazwa względem której sortujemy
     pub reverse_sort: bool, // czy sortujemy rosnąco, czy malejąco
-    pub process_stats_history: HashMap<Pid, ProcessInfoHistory>, // mapa Pid -> ProcessInfoHistory (do rysowania wykresów)
-    pub chart_pid: Pid, // Pid procesu, którego rysujemy wykres
-    pub chart_col: usize, // columna, której rysujemy wykres
+    pub selected_key: Option<ProcessKey>, // proces pod kursorem (zaznaczenie podąża za nim przy sortowaniu)
+    pub freeze_order: bool, // czy odświeżanie danych nie zmienia kolejności wierszy
+    pub process_stats_history: HashMap<ProcessKey, ProcessInfoHistory>, // mapa (Pid, start) -> ProcessInfoHistory (do rysowania wykresów)
+    pub history_retention: Duration, // jak długo trzymać historię procesu po jego zakończeniu
+    pub chart_col: ProcessName, // columna, której rysujemy wykres
     pub stop: bool, // zatrzymanie
     pub cpu_usage_history: VecDeque<f64>, // tablica "najświerzszych" danych o zużyciu procesora
     pub general_info: GeneralInfo, // aktualne dane o całym systemie
-    pub plot_cpu: bool, // czy rysować dane zaznaczonej komórki tabeli, czy zużycie procesora
+    pub chart: ChartTarget, // co rysujemy na wykresie (proces, procesor, rdzeń, dysk)
+    pub core_history: Vec<CoreHistory>, // historia zużycia (i jego podziału) każdego rdzenia
+    pub disk_usage_history: HashMap<String, VecDeque<f64>>, // punkt montowania -> historia zajętości w %
+    pub memory_history: MemoryHistory,
+    pub pressure_history: HashMap<PressureResource, PressureHistory>,
+    pub network_history: HashMap<String, NetworkHistory>, // interfejs -> historia przepustowości
+    pub block_device_history: HashMap<String, BlockDeviceHistory>, // urządzenie -> historia obciążenia
+    pub tree_mode: bool, // czy wyświetlać procesy jako drzewo rodzic -> dzieci
+    pub collapsed: HashSet<Pid>, // zwinięte gałęzie drzewa
+    pub mode: Mode, // aktualny tryb interfejsu
+    pub details_cwd: Option<String>, // katalog roboczy procesu z okienka szczegółów (czytany tylko dla niego)
+    pub status_message: Option<StatusMessage>, // wynik ostatniej akcji (np. wysłania sygnału)
+    pub search: Search, // filtr procesów po nazwie / linii poleceń
+    pub filter: Option<Filter>, // aktywne wyrażenie filtrujące (np. `cpu > 5 and user == "ci"`)
+    pub filter_input: String, // tekst wpisywany w okienku filtra
+    pub filter_error: Option<FilterError>, // błąd parsowania wpisanego filtra
+    pub config_path: Option<PathBuf>, // plik konfiguracyjny, do którego zapisujemy wybór kolumn
+    pub interval: Duration, // co ile zbierane są dane
+    pub history_len: usize, // liczba zapamiętanych pomiarów (długość wykresów)
+    pub panels: Panels, // widoczne panele interfejsu
+    pub color: bool, // czy rysować w kolorach (--no-color)
+    pub units: Units, // jednostki rozmiarów (potęgi 1024 albo 1000)
+    pub cpu_mode: CpuMode, // sposób liczenia procentu CPU procesów (przełączany klawiszem I)
+    pub table_area: Rect, // obszar tabeli procesów z ostatniego rysowania (do obsługi myszy)
+    pub cpu_table_area: Rect, // obszar tabeli rdzeni
+    pub disk_table_area: Rect, // obszar tabeli dysków
+    pub memory_table_area: Rect, // obszar tabeli pamięci
+    pub pressure_table_area: Rect, // obszar tabeli PSI
+    pub network_table_area: Rect, // obszar tabeli interfejsów sieciowych
+    pub block_device_table_area: Rect, // obszar tabeli urządzeń blokowych
 }
 
 impl App {
-    pub fn new() -> Self {
+    pub fn new((items, general_info): Snapshot, settings: &Settings) -> Self {
         // na starcie
-        // pobieram dane
-        let (items, general_info) = get_data();
+        // dostaję pierwszą paczkę danych i ustawienia
         // wyliczam szerokość kolumn
-        let longest_item_lens = Self::constraint_len_calculator(&items);
-        // inicjalizuje struktury
-        let mut cpu_usage_history = VecDeque::new();
-        push_bounded(&mut cpu_usage_history, general_info.get_avg_cpu_usage());
-
+        let columns = settings.columns.clone();
         let items_len = items.len();
 
         // inicjalizacja stanu
-        Self {
+        let mut app = Self {
             state: TableState::default().with_selected(Some(0)),
-            longest_item_lens,
+            saved_columns: columns.clone(),
+            columns,
+            longest_item_lens: Vec::new(),
+            tree_indent: 0,
             scroll_state: ScrollbarState::new(items_len.saturating_sub(1)),
             items,
+            rows: Vec::new(),
             selected_column: 0,
-            sort_tag: ProcessName::CPU,
-            reverse_sort: true,
+            sort_tag: settings.sort,
+            reverse_sort: settings.reverse_sort,
+            selected_key: None,
+            freeze_order: false,
             process_stats_history: HashMap::new(),
-            chart_col: 3,
-            chart_pid: Pid::from_u32(std::process::id()),
+            chart_col: ProcessName::MEM,
+            history_retention: settings.history_retention,
             stop: false,
-            cpu_usage_history,
+            cpu_usage_history: VecDeque::new(),
             general_info,
-            plot_cpu: true,
+            chart: settings.chart.clone(),
+            core_history: Vec::new(),
+            disk_usage_history: HashMap::new(),
+            memory_history: MemoryHistory::default(),
+            pressure_history: HashMap::new(),
+            network_history: HashMap::new(),
+            block_device_history: HashMap::new(),
+            tree_mode: false,
+            collapsed: HashSet::new(),
+            mode: Mode::Normal,
+            details_cwd: None,
+            status_message: None,
+            search: Search::default(),
+            filter: None,
+            filter_input: String::new(),
+            filter_error: None,
+            config_path: None,
+            interval: settings.interval,
+            history_len: settings.history_len,
+            panels: settings.panels,
+            color: settings.color,
+            units: settings.units,
+            cpu_mode: settings.cpu_mode,
+            table_area: Rect::default(),
+            cpu_table_area: Rect::default(),
+            disk_table_area: Rect::default(),
+            memory_table_area: Rect::default(),
+            pressure_table_area: Rect::default(),
+            network_table_area: Rect::default(),
+            block_device_table_area: Rect::default(),
+        };
+        app.longest_item_lens = app.constraint_len_calculator();
+        app.save_general_history();
+        app.sort_data();
+        // błędny filtr nie kończy programu - zostaje pokazany w okienku filtra
+        if let Some(filter) = &settings.filter {
+            app.filter_input = filter.clone();
+            app.submit_filter();
+        }
+        if let Some(pid) = settings.focus_pid {
+            app.focus_process(pid);
+        }
+        app
+    }
+
+    // zaznaczenie procesu i rysowanie jego zużycia procesora
+    pub fn focus_process(&mut self, pid: Pid) {
+        if let Some(row) = self.rows.iter().position(|row| self.items[row.index].pid == pid) {
+            self.select_row(row);
+        }
+        if let Some(key) = self.find_process(pid).map(ProcessInfo::key) {
+            self.chart_col = ProcessName::CPU;
+            self.chart = ChartTarget::Process(key);
+        }
+    }
+    // otwarcie okienka szczegółów procesu
+    pub fn open_details(&mut self, pid: Pid) {
+        self.details_cwd = read_cwd(pid);
+        self.mode = Mode::Details(pid);
+    }
+    // zaznaczenie wiersza - zapamiętuję też proces (PID z czasem startu), żeby zaznaczenie podążało za nim
+    fn select_row(&mut self, row: usize) {
+        self.state.select(Some(row));
+        self.scroll_state = self.scroll_state.position(row);
+        if let Some(visible) = self.rows.get(row) {
+            self.selected_key = Some(self.items[visible.index].key());
         }
     }
     // nastepny rząd dla zaznaczonej komórki
     pub fn next_row(&mut self) {
+        if self.rows.is_empty() {
+            return;
+        }
         let i = match self.state.selected() {
-            Some(i) if i >= self.items.len() - 1 => 0,
+            Some(i) if i >= self.rows.len() - 1 => 0,
             Some(i) => i + 1,
             None => 0,
         };
-        self.state.select(Some(i));
-        self.scroll_state = self.scroll_state.position(i);
+        self.select_row(i);
     }
     // poprzedni rząd dla zaznaczonej komórki
     pub fn previous_row(&mut self) {
+        if self.rows.is_empty() {
+            return;
+        }
         let i = match self.state.selected() {
-            Some(i) if i == 0 => self.items.len() - 1,
+            Some(0) => self.rows.len() - 1,
             Some(i) => i - 1,
             None => 0,
         };
-        self.state.select(Some(i));
-        self.scroll_state = self.scroll_state.position(i);
+        self.select_row(i);
+    }
+    // przesunięcie zaznaczenia o `delta` wierszy bez zawijania (kółko myszy)
+    pub fn scroll_rows(&mut self, delta: isize) {
+        if self.rows.is_empty() {
+            return;
+        }
+        let current = self.state.selected().unwrap_or(0) as isize;
+        let i = (current + delta).clamp(0, self.rows.len() as isize - 1) as usize;
+        self.select_row(i);
     }
     // następna kolumna dla zaznaczonej komórki
     pub fn next_column(&mut self) {
-        self.selected_column = (self.selected_column + 1) % 8;
+        self.selected_column = (self.selected_column + 1) % self.columns.len();
     }
     // poprzednia kolumna dla zaznaczonej komórki
     pub fn previous_column(&mut self) {
         if self.selected_column == 0 {
-            self.selected_column = 7;
+            self.selected_column = self.columns.len() - 1;
         } else {
             self.selected_column -= 1;
         }
     }
+    // kolumna pod kursorem
+    pub fn selected_column_name(&self) -> ProcessName {
+        self.columns[self.selected_column]
+    }
+    // sortowanie według następnej widocznej kolumny
+    pub fn next_sort_column(&mut self) {
+        let i = self.columns.iter().position(|c| *c == self.sort_tag);
+        self.sort_tag = match i {
+            Some(i) => self.columns[(i + 1).min(self.columns.len() - 1)],
+            None => self.columns[0],
+        };
+    }
+    // sortowanie według poprzedniej widocznej kolumny
+    pub fn previous_sort_column(&mut self) {
+        let i = self.columns.iter().position(|c| *c == self.sort_tag);
+        self.sort_tag = self.columns[i.unwrap_or(0).saturating_sub(1)];
+    }
+
+    // ustawienie widocznych kolumn (np. z konfiguracji)
+    pub fn set_columns(&mut self, columns: Vec<ProcessName>) {
+        if columns.is_empty() {
+            return;
+        }
+        self.columns = columns;
+        self.selected_column = self.selected_column.min(self.columns.len() - 1);
+        self.longest_item_lens = self.constraint_len_calculator();
+    }
+
+    // kolejność kolumn w okienku wyboru - najpierw widoczne, potem ukryte
+    pub fn column_picker_order(&self) -> Vec<ProcessName> {
+        let hidden = ProcessName::ALL.iter().filter(|c| !self.columns.contains(c));
+        self.columns.iter().chain(hidden).copied().collect()
+    }
+
+    // pokazanie / ukrycie kolumny (ostatniej widocznej nie da się ukryć)
+    pub fn toggle_column(&mut self, column: ProcessName) {
+        let mut columns = self.columns.clone();
+        match columns.iter().position(|c| *c == column) {
+            Some(_) if columns.len() == 1 => return,
+            Some(i) => {
+                columns.remove(i);
+            }
+            None => columns.push(column),
+        }
+        self.set_columns(columns);
+    }
+
+    // przesunięcie widocznej kolumny w lewo (-1) lub w prawo (+1)
+    pub fn move_column(&mut self, column: ProcessName, delta: isize) {
+        let Some(i) = self.columns.iter().position(|c| *c == column) else {
+            return;
+        };
+        let j = i as isize + delta;
+        if j < 0 || j as usize >= self.columns.len() {
+            return;
+        }
+        let mut columns = self.columns.clone();
+        columns.swap(i, j as usize);
+        self.set_columns(columns);
+    }
+
+    // zapisanie wybranych kolumn do pliku konfiguracyjnego (tylko gdy coś się zmieniło)
+    pub fn save_columns(&mut self) {
+        let Some(path) = &self.config_path else {
+            return;
+        };
+        if self.columns == self.saved_columns {
+            return;
+        }
+        match Config::save_columns(path, &self.columns) {
+            Ok(()) => self.saved_columns = self.columns.clone(),
+            Err(err) => self.status_message = Some(StatusMessage { text: format!("{err:#}"), is_error: true }),
+        }
+    }
+
     // sortowanie danych według aktualnego stanu
     pub fn sort_data(&mut self) {
+        self.sort_items();
+        self.rebuild_rows();
+    }
+
+    // włączenie / wyłączenie zamrożonej kolejności wierszy
+    pub fn toggle_freeze_order(&mut self) {
+        self.freeze_order = !self.freeze_order;
+        if !self.freeze_order {
+            self.sort_data();
+        }
+    }
+
+    fn sort_items(&mut self) {
         self.items.sort_by(|a, b| {
-            let ordering = match self.sort_tag {
-                ProcessName::PID => a.pid.cmp(&b.pid),
-                ProcessName::NAME => a.name.cmp(&b.name),
-                ProcessName::CPU => a.cpu.partial_cmp(&b.cpu).unwrap_or(std::cmp::Ordering::Equal),
-                ProcessName::MEM => a.mem_mb.partial_cmp(&b.mem_mb).unwrap_or(std::cmp::Ordering::Equal),
-                ProcessName::READ => a.read_bytes.cmp(&b.read_bytes),
-                ProcessName::WRITE => a.write_bytes.cmp(&b.write_bytes),
-                ProcessName::TOTAL_READ => a.total_read.cmp(&b.total_read),
-                ProcessName::TOTAL_WRITTEN => a.total_written.cmp(&b.total_written),
-                ProcessName::USER => a.user.cmp(&b.user),
-            };
+            let ordering = a.compare(b, self.sort_tag);
             if self.reverse_sort {
                 ordering.reverse()
             } else {
@@ -111,55 +392,629 @@ impl App {
         });
     }
 
+    // proces w zaznaczonym wierszu
+    pub fn selected_process(&self) -> Option<&ProcessInfo> {
+        let row = self.rows.get(self.state.selected()?)?;
+        self.items.get(row.index)
+    }
+
+    // proces o danym PID (jeżeli nadal istnieje)
+    pub fn find_process(&self, pid: Pid) -> Option<&ProcessInfo> {
+        self.items.iter().find(|proc| proc.pid == pid)
+    }
+
+    // procesy, do których trafi sygnał - sam `target` albo (`tree`) także wszyscy jego potomkowie
+    // (rodzic przed dziećmi, bez wątków)
+    pub fn signal_targets(&self, target: ProcessKey, tree: bool) -> Vec<ProcessKey> {
+        let mut targets = vec![target];
+        if !tree {
+            return targets;
+        }
+        let mut children: HashMap<Pid, Vec<&ProcessInfo>> = HashMap::new();
+        for proc in self.items.iter().filter(|proc| !proc.is_thread) {
+            if let Some(parent) = proc.parent
+                && parent != proc.pid
+            {
+                children.entry(parent).or_default().push(proc);
+            }
+        }
+        let mut seen = HashSet::from([target.pid]);
+        let mut i = 0;
+        while i < targets.len() {
+            for child in children.get(&targets[i].pid).into_iter().flatten() {
+                if seen.insert(child.pid) {
+                    targets.push(child.key());
+                }
+            }
+            i += 1;
+        }
+        targets
+    }
+
+    // wysłanie sygnału do procesu (lub całego poddrzewa), wynik trafia do paska statusu
+    // przed wysłaniem sprawdzam, czy pod PID-em nadal działa ten sam proces (mógł się zakończyć,
+    // a jego PID trafić do zupełnie innego procesu)
+    // `targets` - z `signal_targets`, pierwszy jest wybrany proces
+    pub fn send_signal(&mut self, targets: &[ProcessKey], signal: SignalKind) {
+        let Some(&target) = targets.first() else {
+            return;
+        };
+        if !is_running(target) {
+            self.status_message = Some(StatusMessage {
+                text: format!("Process {} has exited, {} not sent", target.pid, signal.name()),
+                is_error: true,
+            });
+            return;
+        }
+
+        let failures: Vec<String> = targets
+            .iter()
+            .filter_map(|&ProcessKey { pid, start_time }| {
+                if !is_running(ProcessKey { pid, start_time }) {
+                    return Some(format!("{pid}: exited"));
+                }
+                send_signal(pid, signal).err().map(|err| {
+                    if err.kind() == std::io::ErrorKind::PermissionDenied {
+                        format!("{pid}: permission denied")
+                    } else {
+                        format!("{pid}: {err}")
+                    }
+                })
+            })
+            .collect();
+
+        self.status_message = Some(if failures.is_empty() {
+            StatusMessage {
+                text: format!("Sent {} to {} process(es)", signal.name(), targets.len()),
+                is_error: false,
+            }
+        } else {
+            StatusMessage {
+                text: format!("{} failed for {}", signal.name(), failures.join(", ")),
+                is_error: true,
+            }
+        });
+    }
+
+    // przełączanie widoku drzewa
+    pub fn toggle_tree(&mut self) {
+        self.tree_mode = !self.tree_mode;
+        self.rebuild_rows();
+    }
+
+    // zwijanie / rozwijanie gałęzi zaznaczonego procesu
+    pub fn set_collapsed(&mut self, collapsed: bool) {
+        if !self.tree_mode {
+            return;
+        }
+        if let Some(pid) = self.selected_process().map(|proc| proc.pid) {
+            if collapsed {
+                self.collapsed.insert(pid);
+            } else {
+                self.collapsed.remove(&pid);
+            }
+            self.rebuild_rows();
+        }
+    }
+
+    // czy proces przechodzi przez aktywne filtry
+    pub fn is_visible(&self, proc: &ProcessInfo) -> bool {
+        let cpus = self.logical_cpus();
+        self.search.matches(proc) && self.filter.as_ref().is_none_or(|filter| filter.matches(proc, self.cpu_mode, self.units, cpus))
+    }
+
+    // otwarcie okienka filtra z aktualnym wyrażeniem
+    pub fn open_filter_prompt(&mut self) {
+        self.filter_input = self.filter.as_ref().map(|filter| filter.source.clone()).unwrap_or_default();
+        self.filter_error = None;
+        self.mode = Mode::FilterPrompt;
+    }
+
+    // zatwierdzenie wpisanego filtra - pusty tekst usuwa filtr,
+    // przy błędzie okienko zostaje otwarte i pokazuje jego opis
+    pub fn submit_filter(&mut self) {
+        if self.filter_input.trim().is_empty() {
+            self.filter = None;
+        } else {
+            match Filter::parse(&self.filter_input) {
+                Ok(filter) => self.filter = Some(filter),
+                Err(err) => {
+                    self.filter_error = Some(err);
+                    self.mode = Mode::FilterPrompt;
+                    return;
+                }
+            }
+        }
+        self.filter_error = None;
+        self.mode = Mode::Normal;
+        self.rebuild_rows();
+    }
+
+    // wyliczanie wierszy tabeli na podstawie posortowanych `items` i filtrów
+    pub fn rebuild_rows(&mut self) {
+        // zwinięcie zakończonego procesu nie może przejść na nowy proces z tym samym PID
+        let live: HashSet<Pid> = self.items.iter().map(|proc| proc.pid).collect();
+        self.collapsed.retain(|pid| live.contains(pid));
+
+        let visible: Vec<bool> = self.items.iter().map(|proc| self.is_visible(proc)).collect();
+        self.rows = if self.tree_mode {
+            self.tree_rows(visible)
+        } else {
+            (0..self.items.len())
+                .filter(|&index| visible[index])
+                .map(|index| VisibleRow { index, prefix: String::new() })
+                .collect()
+        };
+
+        // kolumna nazwy musi zmieścić też wcięcie najgłębszej gałęzi drzewa
+        let indent = self.rows.iter().map(|row| row.prefix.width()).max().unwrap_or(0);
+        if indent != self.tree_indent {
+            self.tree_indent = indent;
+            self.longest_item_lens = self.constraint_len_calculator();
+        }
+
+        // zaznaczenie podąża za procesem, a gdy go już nie ma (lub jest ukryty),
+        // zostaje w tym samym miejscu tabeli - na sąsiednim wierszu
+        let last = self.rows.len().saturating_sub(1);
+        let anchored = self
+            .selected_key
+            .and_then(|key| self.rows.iter().position(|row| self.items[row.index].key() == key));
+        let row = anchored.unwrap_or_else(|| self.state.selected().unwrap_or(0).min(last));
+        self.scroll_state = self.scroll_state.content_length(self.rows.len());
+        self.select_row(row);
+    }
+
+    // układanie procesów w drzewo - rodzeństwo zachowuje kolejność sortowania,
+    // przy aktywnym filtrze zostają pasujące procesy razem z ich przodkami
+    fn tree_rows(&self, mut keep: Vec<bool>) -> Vec<VisibleRow> {
+        let positions: HashMap<Pid, usize> = self.items.iter().enumerate().map(|(i, proc)| (proc.pid, i)).collect();
+
+        let matched: Vec<usize> = (0..self.items.len()).filter(|&i| keep[i]).collect();
+        for i in matched {
+            let mut current = self.items[i].parent;
+            while let Some(parent) = current
+                && let Some(&p) = positions.get(&parent)
+                && !keep[p]
+            {
+                keep[p] = true;
+                current = self.items[p].parent;
+            }
+        }
+
+        let mut children: HashMap<Pid, Vec<usize>> = HashMap::new();
+        let mut roots = Vec::new();
+
+        for (i, proc) in self.items.iter().enumerate().filter(|&(i, _)| keep[i]) {
+            match proc.parent {
+                Some(parent) if parent != proc.pid && positions.contains_key(&parent) => {
+                    children.entry(parent).or_default().push(i)
+                }
+                _ => roots.push(i),
+            }
+        }
+
+        // przejście w głąb, `indent` to narysowane już linie przodków,
+        // `last` mówi czy węzeł jest ostatnim dzieckiem (None dla korzenia)
+        fn visit(
+            app: &App,
+            index: usize,
+            indent: &str,
+            last: Option<bool>,
+            children: &HashMap<Pid, Vec<usize>>,
+            visited: &mut HashSet<Pid>,
+            rows: &mut Vec<VisibleRow>,
+        ) {
+            let pid = app.items[index].pid;
+            if !visited.insert(pid) {
+                return;
+            }
+            let kids = children.get(&pid).map(|v| v.as_slice()).unwrap_or(&[]);
+            let is_collapsed = app.collapsed.contains(&pid);
+            let marker = match (kids.is_empty(), is_collapsed) {
+                (true, _) => "",
+                (false, true) => "[+] ",
+                (false, false) => "[-] ",
+            };
+            let (branch, child_indent) = match last {
+                None => ("", indent.to_string()),
+                Some(false) => ("├─ ", format!("{indent}│  ")),
+                Some(true) => ("└─ ", format!("{indent}   ")),
+            };
+            rows.push(VisibleRow { index, prefix: format!("{indent}{branch}{marker}") });
+
+            if is_collapsed {
+                return;
+            }
+            for (n, &child) in kids.iter().enumerate() {
+                visit(app, child, &child_indent, Some(n + 1 == kids.len()), children, visited, rows);
+            }
+        }
+
+        let mut rows = Vec::with_capacity(self.items.len());
+        let mut visited = HashSet::new();
+        for root in roots {
+            visit(self, root, "", None, &children, &mut visited, &mut rows);
+        }
+        rows
+    }
+
     // zapisywanie aktualnych danych do mapy
     pub fn save_history_data(&mut self) {
+        let limit = self.history_len;
+        let now = Instant::now();
         for proc in self.items.iter() {
-            let pid = proc.pid;
+            let proc_his = self.process_stats_history.entry(proc.key()).or_insert_with(|| ProcessInfoHistory::new(now));
+            proc_his.last_seen = now;
+
+            push_bounded(&mut proc_his.cpu, proc.cpu.unwrap_or(0.0), limit);
+            push_bounded(&mut proc_his.rss, proc.rss.unwrap_or(0), limit);
+            push_bounded(&mut proc_his.virtual_mem, proc.virtual_mem.unwrap_or(0), limit);
+            push_bounded(&mut proc_his.shared_mem, proc.shared_mem.unwrap_or(0), limit);
+            push_bounded(&mut proc_his.anon_mem, proc.anon_mem.unwrap_or(0), limit);
+            push_bounded(&mut proc_his.swap_mem, proc.swap_mem.unwrap_or(0), limit);
+            push_bounded(&mut proc_his.pss, proc.pss.unwrap_or(0), limit);
+            push_bounded(&mut proc_his.read_bytes, proc.read_bytes.unwrap_or(0), limit);
+            push_bounded(&mut proc_his.write_bytes, proc.write_bytes.unwrap_or(0), limit);
+            push_bounded(&mut proc_his.total_read, proc.total_read.unwrap_or(0), limit);
+            push_bounded(&mut proc_his.total_written, proc.total_written.unwrap_or(0), limit);
+        }
+        // historia zakończonych procesów znika po `history_retention`
+        let retention = self.history_retention;
+        self.process_stats_history.retain(|_, history| now.duration_since(history.last_seen) <= retention);
+    }
+    // funkcja do generowania wykresu
+    // przy aktualnym stanie, zwraca odpowiednią tablice do wyrysowania wykresu
+    pub fn extract_history_data(&self, key: ProcessKey) -> Vec<f64> {
+        // historia mogła już zostać usunięta (proces zakończył się dawno temu)
+        let Some(proces_history) = self.process_stats_history.get(&key) else {
+            return Vec::new();
+        };
 
-            if !self.process_stats_history.contains_key(&pid) {
-                let tmp = ProcessInfoHistory::default();
-                self.process_stats_history.insert(pid, tmp);
+        let bytes = |history: &VecDeque<u64>| history.iter().map(|&v| v as f64).collect();
+        match self.chart_col {
+            ProcessName::MEM => bytes(&proces_history.rss),
+            ProcessName::VIRT => bytes(&proces_history.virtual_mem),
+            ProcessName::SHARED => bytes(&proces_history.shared_mem),
+            ProcessName::ANON => bytes(&proces_history.anon_mem),
+            ProcessName::SWAP => bytes(&proces_history.swap_mem),
+            ProcessName::PSS => bytes(&proces_history.pss),
+            ProcessName::READ => bytes(&proces_history.read_bytes),
+            ProcessName::WRITE => bytes(&proces_history.write_bytes),
+            ProcessName::TOTAL_READ => bytes(&proces_history.total_read),
+            ProcessName::TOTAL_WRITTEN => bytes(&proces_history.total_written),
+            // CPU i kolumny bez własnej historii
+            _ => {
+                let cpus = self.logical_cpus();
+                proces_history.cpu.iter().map(|&v| self.cpu_mode.normalize(v as f64, cpus)).collect()
             }
+        }
+    }
 
-            if let Some(proc_his) = self.process_stats_history.get_mut(&pid) {
-                push_bounded(&mut proc_his.cpu, proc.cpu.unwrap_or(0.0));
-                push_bounded(&mut proc_his.mem_mb, proc.mem_mb.unwrap_or(0.0));
-                push_bounded(&mut proc_his.read_bytes, proc.read_bytes.unwrap_or(0));
-                push_bounded(&mut proc_his.write_bytes, proc.write_bytes.unwrap_or(0));
-                push_bounded(&mut proc_his.total_read, proc.total_read.unwrap_or(0));
-                push_bounded(&mut proc_his.total_written, proc.total_written.unwrap_or(0));
+    // serie danych do wykresu (nazwa w legendzie i wartości) według aktualnego celu
+    pub fn chart_series(&self) -> Vec<(String, Vec<f64>)> {
+        let values = |history: Option<&VecDeque<f64>>| history.map(|h| h.iter().copied().collect()).unwrap_or_default();
+        match &self.chart {
+            ChartTarget::CpuAvg => vec![("Cpu avg usage".to_string(), values(Some(&self.cpu_usage_history)))],
+            ChartTarget::Process(key) => {
+                // zakończony proces (lub jego PID użyty ponownie przez inny)
+                let exited = !self.items.iter().any(|proc| proc.key() == *key);
+                let name = format!("{} \n {:?}{}", key.pid, self.chart_col, if exited { " (exited)" } else { "" });
+                vec![(name, self.extract_history_data(*key))]
+            }
+            ChartTarget::Core(i) => {
+                let name = match self.general_info.cpu_usage_tab.get(*i) {
+                    Some(cpu) => format!("{} usage", cpu.name),
+                    None => format!("cpu{i} usage"),
+                };
+                let history = self.core_history.get(*i);
+                let mut series = vec![(name, values(history.map(|h| &h.usage)))];
+                // części podziału, które choć raz były niezerowe
+                if let Some(history) = history {
+                    for (part, values) in CpuBreakdown::PART_NAMES.iter().zip(&history.parts) {
+                        if values.iter().any(|&v| v > 0.0) {
+                            series.push((part.to_string(), values.iter().copied().collect()));
+                        }
+                    }
+                }
+                series
+            }
+            ChartTarget::Disk(mount) => vec![(format!("{mount} used"), values(self.disk_usage_history.get(mount)))],
+            ChartTarget::Pressure(resource) => {
+                let history = self.pressure_history.get(resource);
+                vec![
+                    (format!("{} some", resource.name()), values(history.map(|h| &h.some))),
+                    (format!("{} full", resource.name()), values(history.map(|h| &h.full))),
+                ]
+            }
+            ChartTarget::Memory => vec![
+                ("used".to_string(), values(Some(&self.memory_history.used))),
+                ("buffers/cache".to_string(), values(Some(&self.memory_history.buffers_cache))),
+                ("swap used".to_string(), values(Some(&self.memory_history.swap))),
+            ],
+            ChartTarget::Network(name) => {
+                let history = self.network_history.get(name);
+                vec![
+                    (format!("{name} RX"), values(history.map(|h| &h.rx))),
+                    (format!("{name} TX"), values(history.map(|h| &h.tx))),
+                ]
+            }
+            ChartTarget::BlockDevice { name, metric } => {
+                let history = self.block_device_history.get(name);
+                match metric {
+                    BlockMetric::Throughput => vec![
+                        (format!("{name} read"), values(history.map(|h| &h.read))),
+                        (format!("{name} write"), values(history.map(|h| &h.write))),
+                    ],
+                    BlockMetric::Iops => vec![(format!("{name} IOPS"), values(history.map(|h| &h.iops)))],
+                    BlockMetric::Await => vec![(format!("{name} await"), values(history.map(|h| &h.await_ms)))],
+                    BlockMetric::Util => vec![(format!("{name} busy"), values(history.map(|h| &h.util)))],
+                }
             }
         }
     }
-    // funkcja do generowania wykresu
-    // przy aktualnym stanie, zwraca odpowiednią tablice do wyrysowania wykresu
-    pub fn extract_history_data(&self) -> Vec<f64> {
-        let pid = self.chart_pid;
-        let proces_history = self.process_stats_history.get(&pid).unwrap();
 
-        match ProcessName::get_name(self.chart_col) {
-            ProcessName::CPU => proces_history.cpu.iter().map(|&v| v as f64).collect(),
-            ProcessName::MEM => proces_history.mem_mb.iter().copied().collect(),
-            ProcessName::READ => proces_history.read_bytes.iter().map(|&v| v as f64).collect(),
-            ProcessName::WRITE => proces_history.write_bytes.iter().map(|&v| v as f64).collect(),
-            ProcessName::TOTAL_READ => proces_history.total_read.iter().map(|&v| v as f64).collect(),
-            ProcessName::TOTAL_WRITTEN => proces_history.total_written.iter().map(|&v| v as f64).collect(),
-            _ => proces_history.cpu.iter().map(|&v| v as f64).collect(),
+    // podpis wartości na osi Y wykresu (kolumny procesu bez własnej historii rysują CPU)
+    pub fn chart_label(&self, value: f64) -> String {
+        match &self.chart {
+            ChartTarget::Network(_) | ChartTarget::BlockDevice { metric: BlockMetric::Throughput, .. } => {
+                format!("{}/s", change_units(value, self.units))
+            }
+            ChartTarget::BlockDevice { metric: BlockMetric::Iops, .. } => format!("{value:.0}"),
+            ChartTarget::BlockDevice { metric: BlockMetric::Await, .. } => format!("{value:.1}ms"),
+            ChartTarget::Process(_)
+                if self.chart_col.is_memory()
+                    || matches!(
+                        self.chart_col,
+                        ProcessName::READ | ProcessName::WRITE | ProcessName::TOTAL_READ | ProcessName::TOTAL_WRITTEN
+                    ) =>
+            {
+                change_units(value, self.units)
+            }
+            _ => format!("{}%", value.round() as u32),
+        }
+    }
+
+    // następny wykres urządzeń blokowych - kolejne wielkości, potem kolejne urządzenia,
+    // a po ostatnim powrót do średniej CPU
+    pub fn next_block_device_chart(&mut self) {
+        let devices = &self.general_info.block_device_tab;
+        let (device, metric) = match &self.chart {
+            ChartTarget::BlockDevice { name, metric } => {
+                let device = devices.iter().position(|device| &device.name == name);
+                let metric = BlockMetric::ALL.iter().position(|m| m == metric).unwrap_or(0);
+                match device {
+                    Some(i) if metric + 1 < BlockMetric::ALL.len() => (i, metric + 1),
+                    Some(i) => (i + 1, 0),
+                    None => (0, 0),
+                }
+            }
+            _ => (0, 0),
+        };
+        self.chart = match devices.get(device) {
+            Some(device) => ChartTarget::BlockDevice { name: device.name.clone(), metric: BlockMetric::ALL[metric] },
+            None => ChartTarget::CpuAvg,
+        };
+    }
+
+    // przełączenie trybu procentu CPU procesów (Irix/Solaris) - historia trzyma surowe wartości,
+    // więc wykres przelicza się od razu, a filtr z `cpu` trzeba wyliczyć ponownie
+    pub fn toggle_cpu_mode(&mut self) {
+        self.cpu_mode = match self.cpu_mode {
+            CpuMode::Irix => CpuMode::Solaris,
+            CpuMode::Solaris => CpuMode::Irix,
+        };
+        self.rebuild_rows();
+    }
+
+    // liczba procesorów logicznych - w trybie Solaris dzieli zużycie procesów
+    pub fn logical_cpus(&self) -> usize {
+        self.general_info.cpu_usage_tab.len().max(1)
+    }
+
+    // tekst komórki tabeli procesów według wybranych jednostek i trybu CPU
+    pub fn column_text(&self, proc: &ProcessInfo, column: ProcessName) -> String {
+        proc.column_text(column, self.units, self.cpu_mode, self.logical_cpus())
+    }
+
+    // kolejność wierszy tabeli procesorów - pogrupowane po rdzeniach fizycznych
+    // (wątki SMT jednego rdzenia obok siebie), bez topologii po numerze
+    pub fn cpu_row_order(&self) -> Vec<usize> {
+        let cpus = &self.general_info.cpu_usage_tab;
+        let mut order: Vec<usize> = (0..cpus.len()).collect();
+        order.sort_by_key(|&i| (cpus[i].topology, cpus[i].index()));
+        order
+    }
+
+    // wykres presji następnego zasobu (po ostatnim wraca do średniej CPU)
+    pub fn next_pressure_chart(&mut self) {
+        let pressure = &self.general_info.pressure;
+        let next = match &self.chart {
+            ChartTarget::Pressure(resource) => pressure.iter().position(|(r, _)| r == resource).map_or(0, |i| i + 1),
+            _ => 0,
+        };
+        self.chart = match pressure.get(next) {
+            Some((resource, _)) => ChartTarget::Pressure(*resource),
+            None => ChartTarget::CpuAvg,
+        };
+    }
+
+    // wykres następnego interfejsu sieciowego (po ostatnim wraca do średniej CPU)
+    pub fn next_network_chart(&mut self) {
+        let networks = &self.general_info.network_tab;
+        let next = match &self.chart {
+            ChartTarget::Network(name) => networks.iter().position(|net| &net.name == name).map_or(0, |i| i + 1),
+            _ => 0,
+        };
+        self.chart = match networks.get(next) {
+            Some(net) => ChartTarget::Network(net.name.clone()),
+            None => ChartTarget::CpuAvg,
+        };
+    }
+
+    // zapisywanie historii procesora, rdzeni, pamięci, presji, dysków i sieci
+    fn save_general_history(&mut self) {
+        let limit = self.history_len;
+        push_bounded(&mut self.cpu_usage_history, self.general_info.get_avg_cpu_usage(), limit);
+
+        let cores = &self.general_info.cpu_usage_tab;
+        self.core_history.resize_with(cores.len(), CoreHistory::default);
+        for (history, cpu) in self.core_history.iter_mut().zip(cores) {
+            push_bounded(&mut history.usage, cpu.usage.unwrap_or(0.0) as f64, limit);
+            if let Some(breakdown) = &cpu.breakdown {
+                for (values, part) in history.parts.iter_mut().zip(breakdown.parts()) {
+                    push_bounded(values, part, limit);
+                }
+            }
+        }
+
+        let mut mounted = HashSet::new();
+        for disk in &self.general_info.disk_tab {
+            let (Some(mount), Some(total), Some(available)) = (&disk.mount_point, disk.total_space, disk.available_space) else {
+                continue;
+            };
+            let used = if total > 0 { (total - available.min(total)) as f64 / total as f64 * 100.0 } else { 0.0 };
+            push_bounded(self.disk_usage_history.entry(mount.clone()).or_default(), used, limit);
+            mounted.insert(mount.clone());
+        }
+        // odmontowane dyski nie zbierają już historii
+        self.disk_usage_history.retain(|mount, _| mounted.contains(mount));
+
+        // pamięć - bez /proc/meminfo tylko zajętość RAM z sysinfo
+        let percent = |part: u64, total: u64| if total > 0 { part as f64 / total as f64 * 100.0 } else { 0.0 };
+        let info = &self.general_info;
+        let total = info.ram_total_memory.unwrap_or(0);
+        let memory = info.memory.unwrap_or_default();
+        push_bounded(&mut self.memory_history.used, percent(info.ram_used_memor.unwrap_or(0), total), limit);
+        push_bounded(&mut self.memory_history.buffers_cache, percent(memory.buffers_cache(), total), limit);
+        push_bounded(&mut self.memory_history.swap, percent(memory.swap_used(), memory.swap_total), limit);
+
+        // bez PSI lista jest pusta i historia też
+        for (resource, pressure) in &self.general_info.pressure {
+            let history = self.pressure_history.entry(*resource).or_default();
+            push_bounded(&mut history.some, pressure.some.avg10, limit);
+            push_bounded(&mut history.full, pressure.full.map_or(0.0, |full| full.avg10), limit);
+        }
+        let pressure = &self.general_info.pressure;
+        self.pressure_history.retain(|resource, _| pressure.iter().any(|(r, _)| r == resource));
+
+        for net in &self.general_info.network_tab {
+            let history = self.network_history.entry(net.name.clone()).or_default();
+            push_bounded(&mut history.rx, net.rx_rate, limit);
+            push_bounded(&mut history.tx, net.tx_rate, limit);
         }
+        let networks = &self.general_info.network_tab;
+        self.network_history.retain(|name, _| networks.iter().any(|net| &net.name == name));
+
+        for device in &self.general_info.block_device_tab {
+            let history = self.block_device_history.entry(device.name.clone()).or_default();
+            push_bounded(&mut history.read, device.read_rate, limit);
+            push_bounded(&mut history.write, device.write_rate, limit);
+            push_bounded(&mut history.iops, device.iops, limit);
+            push_bounded(&mut history.await_ms, device.await_ms.unwrap_or(0.0), limit);
+            push_bounded(&mut history.util, device.util, limit);
+        }
+        let devices = &self.general_info.block_device_tab;
+        self.block_device_history.retain(|name, _| devices.iter().any(|device| &device.name == name));
     }
 
     // zapisywanie pobranych danych o procesach i systemie
-    pub fn update_data(&mut self) {
-        let (items, general_info) = get_data();
-        push_bounded(&mut self.cpu_usage_history, general_info.get_avg_cpu_usage());
+    pub fn update_data(&mut self, (items, general_info): Snapshot) {
+        // przy zamrożonej kolejności procesy zostają na swoich miejscach, a nowe trafiają na koniec
+        let previous_order: HashMap<Pid, usize> = if self.freeze_order {
+            self.items.iter().enumerate().map(|(i, proc)| (proc.pid, i)).collect()
+        } else {
+            HashMap::new()
+        };
         self.items = items;
         self.general_info = general_info;
-        self.sort_data();
+        self.save_general_history();
+        self.sort_items();
+        if self.freeze_order {
+            self.items.sort_by_key(|proc| previous_order.get(&proc.pid).copied().unwrap_or(usize::MAX));
+        }
+        self.rebuild_rows();
         self.save_history_data();
+        // katalog roboczy może się zmienić, a czytam go tylko dla otwartego okienka szczegółów
+        if let Mode::Details(pid) = self.mode {
+            self.details_cwd = read_cwd(pid);
+        }
     }
 
-    // funkcja licząca szerokośc kolumn
-    fn constraint_len_calculator(items: &[ProcessInfo]) -> (u16, u16, u16, u16, u16, u16, u16, u16) {
+    // kliknięcie myszą w punkt (x, y) ekranu
+    pub fn click(&mut self, x: u16, y: u16) {
+        let position = Position::new(x, y);
+        if self.table_area.contains(position) {
+            self.click_process_table(x, y);
+        } else if self.cpu_table_area.contains(position) {
+            // nagłówek tabeli wraca do średniej, wiersz wybiera rdzeń
+            if is_table_header(self.cpu_table_area, y) {
+                self.chart = ChartTarget::CpuAvg;
+            } else if let Some(row) = table_row_at(self.cpu_table_area, 0, y)
+                && let Some(&i) = self.cpu_row_order().get(row)
+            {
+                self.chart = ChartTarget::Core(i);
+            }
+        } else if self.pressure_table_area.contains(position)
+            && let Some(i) = table_row_at(self.pressure_table_area, 0, y)
+            && let Some((resource, _)) = self.general_info.pressure.get(i)
+        {
+            self.chart = ChartTarget::Pressure(*resource);
+        } else if self.memory_table_area.contains(position) {
+            self.chart = ChartTarget::Memory;
+        } else if self.disk_table_area.contains(position)
+            && let Some(i) = table_row_at(self.disk_table_area, 0, y)
+            && let Some(mount) = self.general_info.disk_tab.get(i).and_then(|disk| disk.mount_point.clone())
+        {
+            self.chart = ChartTarget::Disk(mount);
+        } else if self.block_device_table_area.contains(position)
+            && let Some(i) = table_row_at(self.block_device_table_area, 0, y)
+            && let Some(device) = self.general_info.block_device_tab.get(i)
+        {
+            // kolumna decyduje o rysowanej wielkości
+            let metric = match table_column_at(self.block_device_table_area, &BLOCK_DEVICE_TABLE_WIDTHS, 0, x) {
+                Some(3) => BlockMetric::Iops,
+                Some(4) => BlockMetric::Await,
+                Some(5) => BlockMetric::Util,
+                _ => BlockMetric::Throughput,
+            };
+            self.chart = ChartTarget::BlockDevice { name: device.name.clone(), metric };
+        } else if self.network_table_area.contains(position)
+            && let Some(i) = table_row_at(self.network_table_area, 0, y)
+            && let Some(net) = self.general_info.network_tab.get(i)
+        {
+            self.chart = ChartTarget::Network(net.name.clone());
+        }
+    }
+
+    // kliknięcie w tabelę procesów - nagłówek sortuje, komórka zaznacza wiersz i kolumnę
+    fn click_process_table(&mut self, x: u16, y: u16) {
+        let Some(column) = table_column_at(self.table_area, &self.process_table_widths(), PROCESS_TABLE_SELECTION_WIDTH, x) else {
+            return;
+        };
+        if is_table_header(self.table_area, y) {
+            // ponowne kliknięcie w tę samą kolumnę odwraca kierunek
+            let column = self.columns[column];
+            if self.sort_tag == column {
+                self.reverse_sort = !self.reverse_sort;
+            } else {
+                self.sort_tag = column;
+            }
+            self.sort_data();
+        } else if let Some(row) = table_row_at(self.table_area, self.state.offset(), y)
+            && row < self.rows.len()
+        {
+            self.select_row(row);
+            self.selected_column = column;
+        }
+    }
+
+    // szerokości kolumn tabeli procesów
+    pub fn process_table_widths(&self) -> Vec<Constraint> {
+        self.longest_item_lens.iter().map(|len| Constraint::Length(len + 1)).collect()
+    }
+
+    // funkcja licząca szerokośc kolumn (nazwa razem z wcięciem drzewa)
+    fn constraint_len_calculator(&self) -> Vec<u16> {
 
         // funkcja lokalna do wyznaczania naszerszego elementu w kolumnie
         fn max_width_str<I>(iter: I) -> usize
@@ -170,36 +1025,246 @@ impl App {
                 .max()
                 .unwrap_or(0)
         }
-        // dla każdej kolumny wyliczam
-        let pid_len = max(COLUMN_LABEL[0].len() + 5, max_width_str(items.iter().map(|p| p.pid.to_string())));
-        let name_len = max(COLUMN_LABEL[1].len() + 5, max_width_str(items.iter().map(|p| p.name.clone())));
-        let cpu_len = max(COLUMN_LABEL[2].len() + 5, max_width_str(items.iter().map(|p| format!("{:.1}", p.cpu.unwrap_or(0.0)))));
-        let mem_len = max(COLUMN_LABEL[3].len() + 5, max_width_str(items.iter().map(|p| format!("{:.1}", p.mem_mb.unwrap_or(0.0)))));
-        let read_len = max(COLUMN_LABEL[4].len() + 5, max_width_str(items.iter().map(|p| p.read_bytes.unwrap_or(0).to_string())));
-        let write_len = max(COLUMN_LABEL[5].len() + 5, max_width_str(items.iter().map(|p| p.write_bytes.unwrap_or(0).to_string())));
-        let total_read_len = max(COLUMN_LABEL[6].len() + 5, max_width_str(items.iter().map(|p| p.total_read.unwrap_or(0).to_string())));
-        let total_written_len = max(COLUMN_LABEL[7].len() + 5, max_width_str(items.iter().map(|p| p.total_written.unwrap_or(0).to_string())));
-        // zwracanie krotki
-        (
-            pid_len as u16,
-            name_len as u16,
-            cpu_len as u16,
-            mem_len as u16,
-            read_len as u16,
-            write_len as u16,
-            total_read_len as u16,
-            total_written_len as u16,
-        )
+        // dla każdej widocznej kolumny wyliczam
+        self.columns
+            .iter()
+            .map(|&column| {
+                let mut text = max_width_str(self.items.iter().map(|p| self.column_text(p, column)));
+                if column == ProcessName::NAME {
+                    text += self.tree_indent;
+                }
+                max(column.label().len() + 5, text) as u16
+            })
+            .collect()
     }
 }
-// pobieranie świerzych danych o procesach i systemie
-pub fn get_data() -> (Vec<ProcessInfo>, GeneralInfo) {
-    let mut sys = System::new_all();
-    sys.refresh_all();
 
-    let mut process_info_all = Vec::new();
-    for (_pid, process) in sys.processes() {
-        process_info_all.push(ProcessInfo::get_data_from_process(process, &sys));
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DataSource, ScriptedSource};
+
+    // proces z zadanym zużyciem CPU i pamięcią (czas startu = PID, chyba że podano inny)
+    fn process(pid: u32, cpu: f32, rss: u64) -> ProcessInfo {
+        let mut proc = ProcessInfo::with_name(pid, &format!("proc{pid}"));
+        proc.cpu = Some(cpu);
+        proc.rss = Some(rss);
+        proc.start_time = pid as u64;
+        proc
+    }
+
+    fn frame(processes: Vec<ProcessInfo>) -> Snapshot {
+        (processes, GeneralInfo::default())
+    }
+
+    // aplikacja zbudowana z pierwszej próbki scenariusza, tak jak w main.rs
+    fn app(source: &mut ScriptedSource, settings: &Settings) -> App {
+        App::new(source.collect(), settings)
+    }
+
+    // kolejna próbka ze scenariusza, tak jak w pętli programu
+    fn step(app: &mut App, source: &mut ScriptedSource) {
+        app.update_data(source.collect());
+    }
+
+    fn row_pids(app: &App) -> Vec<u32> {
+        app.rows.iter().map(|row| app.items[row.index].pid.as_u32()).collect()
+    }
+
+    fn selected_pid(app: &App) -> Option<u32> {
+        app.selected_process().map(|proc| proc.pid.as_u32())
+    }
+
+    #[test]
+    fn rows_follow_the_sort_column_and_direction() {
+        let mut source = ScriptedSource::new(vec![
+            frame(vec![process(1, 5.0, 300), process(2, 50.0, 100), process(3, 20.0, 200)]),
+            frame(vec![process(1, 90.0, 300), process(2, 0.0, 100), process(3, 20.0, 200)]),
+        ]);
+        let mut app = app(&mut source, &Settings::default());
+        // domyślnie CPU malejąco
+        assert_eq!(row_pids(&app), [2, 3, 1]);
+
+        step(&mut app, &mut source);
+        assert_eq!(row_pids(&app), [1, 3, 2]);
+
+        app.sort_tag = ProcessName::MEM;
+        app.reverse_sort = false;
+        app.sort_data();
+        assert_eq!(row_pids(&app), [2, 3, 1]);
+    }
+
+    #[test]
+    fn frozen_order_keeps_rows_and_appends_new_processes() {
+        let mut source = ScriptedSource::new(vec![frame(vec![process(1, 5.0, 0), process(2, 50.0, 0)])]);
+        source.push_frame(vec![process(1, 90.0, 0), process(2, 0.0, 0), process(3, 99.0, 0)], GeneralInfo::default());
+        let mut app = app(&mut source, &Settings::default());
+        app.toggle_freeze_order();
+
+        step(&mut app, &mut source);
+        assert!(source.is_finished());
+        assert_eq!(row_pids(&app), [2, 1, 3]);
+
+        // odmrożenie od razu sortuje
+        app.toggle_freeze_order();
+        assert_eq!(row_pids(&app), [3, 1, 2]);
     }
-    (process_info_all, GeneralInfo::get_general_data(sys))
-} 
\ No newline at end of file
+
+    #[test]
+    fn selection_follows_the_process_when_rows_move() {
+        let mut source = ScriptedSource::new(vec![
+            frame(vec![process(1, 30.0, 0), process(2, 20.0, 0), process(3, 10.0, 0)]),
+            frame(vec![process(1, 0.0, 0), process(2, 20.0, 0), process(3, 90.0, 0)]),
+        ]);
+        let mut app = app(&mut source, &Settings::default());
+        app.next_row();
+        assert_eq!(selected_pid(&app), Some(2));
+
+        step(&mut app, &mut source);
+        assert_eq!(row_pids(&app), [3, 2, 1]);
+        assert_eq!(selected_pid(&app), Some(2));
+        assert_eq!(app.state.selected(), Some(1));
+    }
+
+    #[test]
+    fn selection_stays_in_place_when_the_process_exits() {
+        let mut source = ScriptedSource::new(vec![
+            frame(vec![process(1, 30.0, 0), process(2, 20.0, 0), process(3, 10.0, 0)]),
+            frame(vec![process(1, 30.0, 0), process(3, 10.0, 0)]),
+            frame(vec![process(1, 30.0, 0)]),
+        ]);
+        let mut app = app(&mut source, &Settings::default());
+        app.next_row();
+        assert_eq!(selected_pid(&app), Some(2));
+
+        // sąsiedni wiersz zajmuje miejsce zakończonego procesu
+        step(&mut app, &mut source);
+        assert_eq!(selected_pid(&app), Some(3));
+
+        // a gdy wierszy jest mniej, zaznaczenie przesuwa się na ostatni
+        step(&mut app, &mut source);
+        assert_eq!(selected_pid(&app), Some(1));
+        assert_eq!(app.state.selected(), Some(0));
+    }
+
+    #[test]
+    fn selection_does_not_follow_a_reused_pid() {
+        let mut reused = process(2, 90.0, 0);
+        reused.start_time = 100;
+        let mut source = ScriptedSource::new(vec![
+            frame(vec![process(1, 30.0, 0), process(2, 20.0, 0), process(3, 10.0, 0)]),
+            frame(vec![process(1, 30.0, 0), reused, process(3, 10.0, 0)]),
+        ]);
+        let mut app = app(&mut source, &Settings::default());
+        app.next_row();
+        assert_eq!(selected_pid(&app), Some(2));
+
+        // PID 2 należy już do innego procesu - zaznaczenie zostaje w tym samym miejscu tabeli
+        step(&mut app, &mut source);
+        assert_eq!(row_pids(&app), [2, 1, 3]);
+        assert_eq!(selected_pid(&app), Some(1));
+    }
+
+    #[test]
+    fn selection_moves_to_a_visible_row_when_the_process_is_filtered_out() {
+        let mut source = ScriptedSource::new(vec![frame(vec![process(1, 30.0, 0), process(2, 20.0, 0)])]);
+        let mut app = app(&mut source, &Settings::default());
+        app.focus_process(Pid::from_u32(2));
+
+        app.filter_input = "pid == 1".to_string();
+        app.submit_filter();
+        assert_eq!(row_pids(&app), [1]);
+        assert_eq!(selected_pid(&app), Some(1));
+
+        app.filter_input.clear();
+        app.submit_filter();
+        assert_eq!(row_pids(&app), [1, 2]);
+        assert_eq!(selected_pid(&app), Some(1));
+    }
+
+    #[test]
+    fn history_is_bounded_by_the_history_length() {
+        let settings = Settings { history_len: 3, ..Settings::default() };
+        let frames = (0..5).map(|i| frame(vec![process(1, i as f32, 0)])).collect();
+        let mut source = ScriptedSource::new(frames);
+        let mut app = app(&mut source, &settings);
+        app.save_history_data();
+        for _ in 0..4 {
+            step(&mut app, &mut source);
+        }
+
+        let key = ProcessKey { pid: Pid::from_u32(1), start_time: 1 };
+        assert_eq!(app.process_stats_history[&key].cpu, [2.0, 3.0, 4.0]);
+        assert_eq!(app.cpu_usage_history.len(), 3);
+    }
+
+    #[test]
+    fn history_of_an_exited_process_is_kept_for_the_retention_time() {
+        let mut source = ScriptedSource::new(vec![
+            frame(vec![process(1, 10.0, 0), process(2, 20.0, 0)]),
+            frame(vec![process(1, 10.0, 0)]),
+        ]);
+        let mut app = app(&mut source, &Settings::default());
+        app.save_history_data();
+        let exited = ProcessKey { pid: Pid::from_u32(2), start_time: 2 };
+        app.chart = ChartTarget::Process(exited);
+        app.chart_col = ProcessName::CPU;
+
+        step(&mut app, &mut source);
+        assert!(app.process_stats_history.contains_key(&exited));
+        let series = app.chart_series();
+        assert!(series[0].0.contains("(exited)"));
+        // tryb Solaris przy jednym procesorze nie zmienia wartości
+        assert_eq!(series[0].1, [20.0]);
+    }
+
+    #[test]
+    fn history_of_an_exited_process_is_dropped_after_the_retention_time() {
+        let settings = Settings { history_retention: Duration::ZERO, ..Settings::default() };
+        let mut source = ScriptedSource::new(vec![
+            frame(vec![process(1, 10.0, 0), process(2, 20.0, 0)]),
+            frame(vec![process(1, 10.0, 0)]),
+        ]);
+        let mut app = app(&mut source, &settings);
+        app.save_history_data();
+        std::thread::sleep(Duration::from_millis(2));
+
+        step(&mut app, &mut source);
+        let keys: Vec<u32> = app.process_stats_history.keys().map(|key| key.pid.as_u32()).collect();
+        assert_eq!(keys, [1]);
+    }
+
+    #[test]
+    fn reused_pid_starts_a_new_history() {
+        let mut reused = process(1, 70.0, 0);
+        reused.start_time = 100;
+        let mut source = ScriptedSource::new(vec![frame(vec![process(1, 10.0, 0)]), frame(vec![reused])]);
+        let mut app = app(&mut source, &Settings::default());
+        app.save_history_data();
+
+        step(&mut app, &mut source);
+        let old = ProcessKey { pid: Pid::from_u32(1), start_time: 1 };
+        let new = ProcessKey { pid: Pid::from_u32(1), start_time: 100 };
+        assert_eq!(app.process_stats_history[&old].cpu, [10.0]);
+        assert_eq!(app.process_stats_history[&new].cpu, [70.0]);
+    }
+
+    #[test]
+    fn signal_targets_cover_descendants_without_threads() {
+        let child = |pid: u32, parent: u32| {
+            let mut proc = process(pid, 0.0, 0);
+            proc.parent = Some(Pid::from_u32(parent));
+            proc
+        };
+        let mut thread = child(5, 2);
+        thread.is_thread = true;
+        let mut source =
+            ScriptedSource::new(vec![frame(vec![child(4, 3), process(1, 0.0, 0), child(3, 2), child(2, 1), thread, child(6, 1)])]);
+        let app = app(&mut source, &Settings::default());
+        let root = app.find_process(Pid::from_u32(2)).unwrap().key();
+
+        let pids = |tree| app.signal_targets(root, tree).iter().map(|key| key.pid.as_u32()).collect::<Vec<_>>();
+        assert_eq!(pids(false), [2]);
+        assert_eq!(pids(true), [2, 3, 4]);
+    }
+}
@@ -2,28 +2,66 @@ use ratatui::{
     layout::{Constraint, Layout, Margin, Rect},
     style::{Color, Modifier, Style},
     symbols,
-    text::Text,
+    text::{Line, Text},
     widgets::{
-        Axis, Block, BorderType, Cell, Chart, Dataset, GraphType, HighlightSpacing, Paragraph, Row,
-        Scrollbar, ScrollbarOrientation, Table,
+        Axis, Block, BorderType, Cell, Chart, Clear, Dataset, GraphType, HighlightSpacing, Paragraph, Row,
+        Scrollbar, ScrollbarOrientation, Table, Wrap,
     },
     Frame,
 };
+use std::collections::HashSet;
+
 use ratatui::prelude::Span;
+use sysinfo::Pid;
+
+use crate::models::{INFO_TEXT, ITEM_HEIGHT, MEMORY_PANEL_HEIGHT, MemInfo, PressureStall, ProcessKey, ProcessName, SignalKind, TaskCounts};
+use crate::utils::{change_units, CpuMode, format_duration, format_frequency, format_option, format_option_units, format_timestamp, percent_bar};
+use super::state::{App, ChartTarget, Mode, BLOCK_DEVICE_TABLE_WIDTHS};
+
+// kolory i skróty części czasu procesora (w kolejności z CpuBreakdown::PART_NAMES)
+const CPU_PART_COLORS: [Color; 7] = [Color::Green, Color::Blue, Color::Red, Color::Yellow, Color::Magenta, Color::LightMagenta, Color::Cyan];
+const CPU_PART_LABELS: [&str; 7] = ["us", "ni", "sy", "wa", "hi", "si", "st"];
+
+// skala kolorów mapy cieplnej (od zielonego do czerwonego, co 10%) w palecie 256 kolorów
+const HEAT_COLORS: [u8; 10] = [22, 28, 34, 70, 106, 142, 178, 214, 202, 196];
+
+fn heat_color(usage: f64) -> Color {
+    let step = ((usage / 10.0) as usize).min(HEAT_COLORS.len() - 1);
+    Color::Indexed(HEAT_COLORS[step])
+}
+
+// prostokąt na środku `area` o zadanych rozmiarach procentowych (dla okienek)
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let [_, vertical, _] = Layout::vertical([
+        Constraint::Percentage((100 - percent_y) / 2),
+        Constraint::Percentage(percent_y),
+        Constraint::Percentage((100 - percent_y) / 2),
+    ])
+    .areas(area);
+    let [_, center, _] = Layout::horizontal([
+        Constraint::Percentage((100 - percent_x) / 2),
+        Constraint::Percentage(percent_x),
+        Constraint::Percentage((100 - percent_x) / 2),
+    ])
+    .areas(vertical);
+    center
+}
 
-use crate::models::{CHART_RANGE, COLUMN_LABEL, INFO_TEXT, ITEM_HEIGHT, ProcessName};
-use crate::utils::{change_units, format_option, format_option_units};
-use super::state::App;
+// podział obszaru tylko między widoczne części (ukryte dostają pusty prostokąt)
+fn split_visible<const N: usize>(area: Rect, vertical: bool, parts: [(bool, Constraint); N]) -> [Rect; N] {
+    let constraints = parts.iter().filter(|(visible, _)| *visible).map(|(_, constraint)| *constraint);
+    let layout = if vertical { Layout::vertical(constraints) } else { Layout::horizontal(constraints) };
+    let mut rects = layout.split(area).to_vec().into_iter();
+    parts.map(|(visible, _)| if visible { rects.next().unwrap_or_default() } else { Rect::default() })
+}
 
 impl App {
     // funkcja renderująca główną tabele
     pub fn render_table(&mut self, frame: &mut Frame, area: Rect) {
-        let mut header_names: Vec<String> = COLUMN_LABEL.iter().map(|s| s.to_string()).collect();
-        // znak "kierunku" sortowania
-        if self.reverse_sort {
-            header_names[self.sort_tag.get_index()] += " (v) ";
-        } else {
-            header_names[self.sort_tag.get_index()] += " (^) ";
+        let mut header_names: Vec<String> = self.columns.iter().map(|c| c.label().to_string()).collect();
+        // znak "kierunku" sortowania (o ile kolumna sortowania jest widoczna)
+        if let Some(i) = self.columns.iter().position(|c| *c == self.sort_tag) {
+            header_names[i] += if self.reverse_sort { " (v) " } else { " (^) " };
         }
 
         // mapowanie na celle i row
@@ -31,23 +69,18 @@ impl App {
         let header = Row::new(header_cells).style(Style::default()).height(ITEM_HEIGHT);
 
         // liczenie wierszy (za pomocą map)
-        let rows = self.items.iter().enumerate().map(|(i, proc)| {
-            let columns = [
-                proc.pid.to_string(),
-                proc.name.clone(),
-                format_option(proc.cpu.map(|v| format!("{:.1}", v))),
-                format_option_units(proc.mem_mb),
-                format_option_units(proc.read_bytes.map(|v| v as f64)),
-                format_option_units(proc.write_bytes.map(|v| v as f64)),
-                format_option_units(proc.total_read.map(|v| v as f64)),
-                format_option_units(proc.total_written.map(|v| v as f64)),
-            ];
+        let rows = self.rows.iter().enumerate().map(|(i, row)| {
+            let proc = &self.items[row.index];
+            let columns = self.columns.iter().map(|&column| match column {
+                // gałęzie drzewa doklejam przed nazwą
+                ProcessName::NAME => format!("{}{}", row.prefix, proc.name),
+                _ => self.column_text(proc, column),
+            });
 
             let cells = columns
-                .iter()
                 .enumerate()
                 .map(|(idx, content)| {
-                    let mut cell = Cell::from(Text::from(format!("{content}")));
+                    let mut cell = Cell::from(Text::from(content.to_string()));
                     // jeżeli to jest ta zaznaczona komórka
                     if i == self.state.selected().unwrap_or(0) && idx == self.selected_column {
                         cell = cell.style(Style::default().add_modifier(Modifier::REVERSED));
@@ -60,21 +93,33 @@ impl App {
         }).collect::<Vec<_>>();
 
         // szerokości
-        let widths = [
-            Constraint::Length(self.longest_item_lens.0 + 1),
-            Constraint::Length(self.longest_item_lens.1 + 1),
-            Constraint::Length(self.longest_item_lens.2 + 1),
-            Constraint::Length(self.longest_item_lens.3 + 1),
-            Constraint::Length(self.longest_item_lens.4 + 1),
-            Constraint::Length(self.longest_item_lens.5 + 1),
-            Constraint::Length(self.longest_item_lens.6 + 1),
-            Constraint::Length(self.longest_item_lens.7 + 1),
-        ];
+        let widths = self.process_table_widths();
+        // pasek statusu (np. wynik wysłania sygnału)
+        let mut block = Block::default().borders(ratatui::widgets::Borders::ALL).border_type(BorderType::Rounded);
+        // aktywne filtry w tytule tabeli
+        if self.mode == Mode::Search || self.search.is_active() {
+            let cursor = if self.mode == Mode::Search { "_" } else { "" };
+            let style = if self.search.error.is_some() { Style::default().fg(Color::Red) } else { Style::default().fg(Color::Yellow) };
+            block = block.title(Span::styled(format!(" {}{cursor} ", self.search.describe()), style));
+        }
+        if let Some(filter) = &self.filter {
+            block = block.title(Span::styled(format!(" filter: {} ", filter.source), Style::default().fg(Color::Yellow)));
+        }
+        if self.freeze_order {
+            block = block.title(Span::styled(" frozen ", Style::default().fg(Color::Cyan)));
+        }
+        if self.rows.len() != self.items.len() {
+            block = block.title(format!(" {}/{} ", self.rows.len(), self.items.len()));
+        }
+        if let Some(message) = &self.status_message {
+            let color = if message.is_error { Color::Red } else { Color::Green };
+            block = block.title_bottom(Span::styled(format!(" {} ", message.text), Style::default().fg(color)));
+        }
         // i tworzenie tabeli
         let table = Table::new(rows, &widths)
             .header(header)
-            .block(Block::default().borders(ratatui::widgets::Borders::ALL).border_type(BorderType::Rounded))
-            .widths(&widths)
+            .block(block)
+            .widths(widths)
             .highlight_symbol(">> ")
             .highlight_spacing(HighlightSpacing::Always);
         // zwracanie w frame
@@ -94,6 +139,34 @@ impl App {
             &mut self.scroll_state,
         );
     }
+    // pasek nagłówka - nazwa maszyny, system, czas działania, obciążenie i liczba zadań
+    pub fn render_header(&self, frame: &mut Frame, area: Rect) {
+        let host = &self.general_info.host;
+        let tasks = |name: &str, counts: &TaskCounts| {
+            let zombie_style = if counts.zombie > 0 { Style::default().fg(Color::Red) } else { Style::default() };
+            vec![
+                Span::raw(format!(
+                    " | {name} {}: {} run, {} sleep, {} stop, ",
+                    counts.total, counts.running, counts.sleeping, counts.stopped
+                )),
+                Span::styled(format!("{} zombie", counts.zombie), zombie_style),
+            ]
+        };
+        let [one, five, fifteen] = host.load_average;
+        let mut spans = vec![
+            Span::styled(format!(" {}", format_option(host.hostname.as_deref())), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::raw(format!(
+                " | {} | kernel {} | up {} | load {one:.2} {five:.2} {fifteen:.2}",
+                format_option(host.os.as_deref()),
+                format_option(host.kernel.as_deref()),
+                format_duration(host.uptime),
+            )),
+        ];
+        spans.extend(tasks("Tasks", &host.processes));
+        spans.extend(tasks("Threads", &host.threads));
+        spans.push(Span::raw(format!(" | cpu% {}", self.cpu_mode.name())));
+        frame.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
     // generowanie instrukcji
     pub fn render_footer(&self, frame: &mut Frame, area: Rect) {
         // wycentrowany paragraf
@@ -103,55 +176,82 @@ impl App {
             .block(Block::default().border_type(BorderType::Double).borders(ratatui::widgets::Borders::ALL));
         frame.render_widget(info_footer, area);
     }
+    // okienko filtra - rysowane w miejscu instrukcji
+    pub fn render_filter_prompt(&self, frame: &mut Frame, area: Rect) {
+        let mut lines = vec![Line::from(vec![
+            Span::styled("> ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(format!("{}_", self.filter_input)),
+        ])];
+        match &self.filter_error {
+            // znacznik pod miejscem błędu
+            Some(err) => lines.push(Line::styled(
+                format!("  {}^ {}", " ".repeat(err.position), err),
+                Style::default().fg(Color::Red),
+            )),
+            None => {
+                let fields: Vec<&str> = ProcessName::ALL.iter().map(|field| field.field_name()).collect();
+                lines.push(Line::styled(
+                    format!("  fields: {} | ops: == != > >= < <= ~ !~ | and or not ( )", fields.join(" ")),
+                    Style::default().fg(Color::Gray),
+                ))
+            }
+        }
+        let prompt = Paragraph::new(lines).block(
+            Block::default()
+                .border_type(BorderType::Double)
+                .borders(ratatui::widgets::Borders::ALL)
+                .title(" Filter (Enter) apply | (Esc) cancel | empty clears "),
+        );
+        frame.render_widget(prompt, area);
+    }
     // generowanie wykresu
     pub fn render_animated_chart(&self, frame: &mut Frame, area: Rect) {
-        // pobieranie odpowieniej tablicy do wykresu
-        let values = if !self.plot_cpu {
-            self.extract_history_data()
-        } else {
-            self.cpu_usage_history.iter().cloned().collect()
-        };
-        let len = values.len();
+        // pobieranie odpowieniej tablicy (lub kilku) do wykresu
+        let series = self.chart_series();
         // preparing danych (żeby wykres był dosunięty do prawej)
-        let data: Vec<(f64, f64)> = (0..60)
-            .map(|i| {
-                let index = i as isize - (60 - len) as isize;
-                let value = if index >= 0 { values[index as usize] } else { 0.0 };
-                ((i + 1) as f64, value)
+        let points = self.history_len;
+        let data: Vec<Vec<(f64, f64)>> = series
+            .iter()
+            .map(|(_, values)| {
+                let len = values.len();
+                (0..points)
+                    .map(|i| {
+                        let index = i as isize - points.saturating_sub(len) as isize;
+                        let value = if index >= 0 { values[index as usize] } else { 0.0 };
+                        ((i + 1) as f64, value)
+                    })
+                    .collect()
             })
             .collect();
 
+        // oś czasu - długość historii razy okres odświeżania
+        let span = points as f64 * self.interval.as_secs_f64();
         let x_labels = vec![
-            Span::styled(format!("{}s", CHART_RANGE[1]), Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(format!("{}s", (CHART_RANGE[0] + CHART_RANGE[1]) / 2.0)),
-            Span::styled(format!("{}s", CHART_RANGE[0]), Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled(format!("{}s", span), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(format!("{}s", span / 2.0)),
+            Span::styled("0s", Style::default().add_modifier(Modifier::BOLD)),
         ];
 
-        let describe_name = if self.plot_cpu {
-            "Cpu avg usage".to_string()
-        } else {
-            format!("{} \n {:?}", self.chart_pid, ProcessName::get_name(self.chart_col))
-        };
-
-        let datasets = vec![Dataset::default()
-            .name(describe_name)
-            .marker(symbols::Marker::Braille)
-            .style(Style::default().fg(Color::Cyan))
-            .graph_type(GraphType::Line)
-            .data(&data)];
+        // kolejne serie w kolejnych kolorach
+        const SERIES_COLORS: [Color; 3] = [Color::Cyan, Color::Magenta, Color::Yellow];
+        let datasets = series
+            .iter()
+            .zip(&data)
+            .enumerate()
+            .map(|(i, ((name, _), data))| {
+                Dataset::default()
+                    .name(name.clone())
+                    .marker(symbols::Marker::Braille)
+                    .style(Style::default().fg(SERIES_COLORS[i % SERIES_COLORS.len()]))
+                    .graph_type(GraphType::Line)
+                    .data(data)
+            })
+            .collect::<Vec<_>>();
 
-        let max_val = values.iter().copied().fold(f64::NEG_INFINITY, |a, b| a.max(b));
+        let max_val = series.iter().flat_map(|(_, values)| values).copied().fold(0.0, |a: f64, b| a.max(b));
 
-        let y_max_label = if self.chart_col < 3 || self.plot_cpu {
-            format!("{}%", max_val.round() as u32)
-        } else {
-            change_units(max_val)
-        };
-        let y_mid_label = if self.chart_col < 3 || self.plot_cpu {
-            format!("{}%", (max_val / 2.0).round() as u32)
-        } else {
-            change_units(max_val / 2.0)
-        };
+        let y_max_label = self.chart_label(max_val);
+        let y_mid_label = self.chart_label(max_val / 2.0);
 
         let chart = Chart::new(datasets)
             .block(Block::bordered())
@@ -160,57 +260,171 @@ impl App {
                     .title("Time")
                     .style(Style::default().fg(Color::Gray))
                     .labels(x_labels)
-                    .bounds(CHART_RANGE),
+                    .bounds([0.0, points as f64]),
             )
             .y_axis(
                 Axis::default()
                     .title("Value")
                     .style(Style::default().fg(Color::Gray))
-                    .labels(vec!["0".into(), y_mid_label.into(), y_max_label])
+                    .labels(vec!["0".into(), y_mid_label, y_max_label])
                     .bounds([0.0, max_val]),
             );
 
         frame.render_widget(chart, area);
     }
-    // renderuje tablice zużycia wątków procesora
+    // renderuje mapę cieplną zużycia rdzeni - wiersz na rdzeń (z numerem), kolumna na pomiar
+    // (najnowsze po prawej); rdzenie, które się nie mieszczą, są obcinane z informacją w tytule
+    pub fn render_heatmap(&self, frame: &mut Frame, area: Rect) {
+        let mut legend = vec![Span::raw(" 0% ")];
+        legend.extend(HEAT_COLORS.iter().map(|&c| Span::styled("█", Style::default().fg(Color::Indexed(c)))));
+        legend.push(Span::raw(" 100% "));
+        let cores = self.core_history.len();
+        let visible = (area.height.saturating_sub(2) as usize).min(cores);
+        let title = if visible < cores {
+            format!(" CPU heatmap (cores 0-{} of {cores}) ", visible.saturating_sub(1))
+        } else {
+            " CPU heatmap ".to_string()
+        };
+        let block = Block::default()
+            .borders(ratatui::widgets::Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title(title)
+            .title(Line::from(legend).right_aligned());
+        let inner = block.inner(area);
+
+        let label_width = cores.saturating_sub(1).to_string().len();
+        let samples = (inner.width as usize).saturating_sub(label_width + 1);
+
+        let lines = self.core_history.iter().take(visible).enumerate().map(|(core, history)| {
+            let usage = &history.usage;
+            let mut spans = vec![Span::raw(format!("{core:>label_width$} "))];
+            // historia dosunięta do prawej - brak pomiaru (początek historii) zostaje pusty
+            spans.extend((0..samples).map(|column| {
+                match (column + usage.len()).checked_sub(samples).and_then(|i| usage.get(i)) {
+                    Some(&value) => Span::styled("█", Style::default().fg(heat_color(value))),
+                    None => Span::raw(" "),
+                }
+            }));
+            Line::from(spans)
+        }).collect::<Vec<_>>();
+
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    // renderuje tablice zużycia wątków procesora - paski z podziałem czasu (user, system, iowait, ...)
     // analogiczna co render_table
     pub fn render_cpu_usage(&mut self, frame: &mut Frame, area: Rect) {
-        let header_names = vec!["Cpu name".to_string(), "Usage %".to_string()];
-        let header_cells: Vec<Cell> = header_names.into_iter().map(Cell::from).collect();
-        let header = Row::new(header_cells).style(Style::default()).height(ITEM_HEIGHT);
+        // legenda w tytule ramki - skróty jak w `top`
+        let mut legend = vec![Span::raw(" ")];
+        for (label, color) in CPU_PART_LABELS.iter().zip(CPU_PART_COLORS) {
+            legend.push(Span::styled(format!("{label} "), Style::default().fg(color)));
+        }
+        let header = Row::new(vec![Cell::from("Cpu"), Cell::from("Usage"), Cell::from("%")])
+            .style(Style::default())
+            .height(ITEM_HEIGHT);
 
-        let rows = self.general_info.cpu_usage_tab.iter().map(|cpu_info| {
-            let columns = [
-                cpu_info.name.to_string(),
-                format_option(cpu_info.usage.map(|v| format!("{:.1}", v))),
+        // nazwa z ewentualnym "└" przed nią
+        let name_width = self.general_info.cpu_usage_tab.iter().map(|cpu| cpu.name.len() as u16 + 1).max().unwrap_or(0).max(4);
+        let widths = [Constraint::Length(name_width), Constraint::Fill(1), Constraint::Length(5)];
+        // szerokość paska - obszar bez ramki, pozostałych kolumn i odstępów
+        let bar_width = area.width.saturating_sub(2 + name_width + 5 + 2) as usize;
+
+        let cpus = &self.general_info.cpu_usage_tab;
+        let order = self.cpu_row_order();
+        let rows = order.iter().enumerate().map(|(row, &i)| {
+            let cpu_info = &cpus[i];
+            // kolejne wątki tego samego rdzenia fizycznego oznaczone "└"
+            let sibling = row > 0 && cpu_info.topology.is_some() && cpus[order[row - 1]].topology == cpu_info.topology;
+            let label = if sibling { format!("└{}", cpu_info.name) } else { cpu_info.name.clone() };
+            // bez /proc/stat pasek ma jeden kolor (samo zużycie)
+            let (parts, usage) = match &cpu_info.breakdown {
+                Some(breakdown) => (breakdown.parts().to_vec(), Some(100.0 - breakdown.idle)),
+                None => (vec![cpu_info.usage.unwrap_or(0.0) as f64], cpu_info.usage.map(f64::from)),
+            };
+            let mut spans = Vec::new();
+            let (mut sum, mut filled) = (0.0, 0);
+            for (part, color) in parts.iter().zip(CPU_PART_COLORS) {
+                // granice liczone od sumy, żeby zaokrąglenia się nie kumulowały
+                sum += part;
+                let end = ((sum.min(100.0) / 100.0 * bar_width as f64).round() as usize).max(filled);
+                spans.push(Span::styled("█".repeat(end - filled), Style::default().fg(color)));
+                filled = end;
+            }
+            spans.push(Span::styled("░".repeat(bar_width.saturating_sub(filled)), Style::default().fg(Color::DarkGray)));
+
+            let cells = vec![
+                Cell::from(label),
+                Cell::from(Line::from(spans)),
+                Cell::from(format_option(usage.map(|v| format!("{v:.1}")))),
             ];
-            let cells = columns.iter().map(|content| Cell::from(Text::from(content.clone()))).collect::<Vec<_>>();
             Row::new(cells).height(ITEM_HEIGHT)
         }).collect::<Vec<_>>();
 
-        let widths = [Constraint::Percentage(50), Constraint::Percentage(50)];
-
         let table = Table::new(rows, &widths)
             .header(header)
-            .block(Block::default().borders(ratatui::widgets::Borders::ALL).border_type(BorderType::Rounded))
-            .widths(&widths);
+            .block(
+                Block::default()
+                    .borders(ratatui::widgets::Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .title(Line::from(legend)),
+            )
+            .widths(widths);
 
         frame.render_widget(table, area);
     }
-    // renderuje tablice zużycia ramu
+    // renderuje tablice zużycia ramu i swapu
     // analogiczna co render_table
     pub fn render_ram_usage(&mut self, frame: &mut Frame, area: Rect) {
-        let rows = vec![
-            Row::new(vec!["Ram total memory".to_string(), format_option_units(self.general_info.ram_total_memory.map(|v| v as f64))]).height(ITEM_HEIGHT),
-            Row::new(vec!["Ram available memory".to_string(), format_option_units(self.general_info.ram_available_memory.map(|v| v as f64))]).height(ITEM_HEIGHT),
-            Row::new(vec!["Ram used memory".to_string(), format_option_units(self.general_info.ram_used_memor.map(|v| v as f64))]).height(ITEM_HEIGHT),
+        let info = &self.general_info;
+        let memory = info.memory;
+        let total = info.ram_total_memory;
+        // wiersze: nazwa, wartość i całość, względem której liczony jest procent
+        let ram = |value: Option<u64>| (value, total);
+        let meminfo = |field: fn(&MemInfo) -> u64| (memory.as_ref().map(field), total);
+        let swap = (memory.map(|m| m.swap_used()), memory.map(|m| m.swap_total));
+        // dwie kolumny: po lewej RAM i swap, po prawej rozbicie z /proc/meminfo
+        let lines = [
+            [("Total", (total, None)), ("Buffers", meminfo(|m| m.buffers))],
+            [("Available", ram(info.ram_available_memory)), ("Page cache", meminfo(|m| m.cached))],
+            [("Used", ram(info.ram_used_memor)), ("Shared", meminfo(|m| m.shmem))],
+            [("Swap total", (memory.map(|m| m.swap_total), None)), ("Slab", meminfo(|m| m.slab))],
+            [("Swap used", swap), ("Dirty", meminfo(|m| m.dirty))],
+            [("", (None, None)), ("Writeback", meminfo(|m| m.writeback))],
         ];
+        let rows = lines.into_iter().map(|line| {
+            let cells = line.into_iter().flat_map(|(name, (value, whole))| {
+                let size = if name.is_empty() { String::new() } else { format_option_units(value.map(|v| v as f64), self.units) };
+                let percent = match (value, whole) {
+                    (Some(value), Some(whole)) if whole > 0 => format!("{:.1}%", value as f64 / whole as f64 * 100.0),
+                    _ => String::new(),
+                };
+                [name.to_string(), size, percent]
+            });
+            Row::new(cells.collect::<Vec<_>>()).height(ITEM_HEIGHT)
+        }).collect::<Vec<_>>();
 
-        let widths = [Constraint::Percentage(50), Constraint::Percentage(50)];
+        let widths = [
+            Constraint::Percentage(18),
+            Constraint::Percentage(18),
+            Constraint::Percentage(14),
+            Constraint::Percentage(18),
+            Constraint::Percentage(18),
+            Constraint::Percentage(14),
+        ];
 
+        let mut style = Style::default();
+        // pamięć aktualnie na wykresie
+        if self.chart == ChartTarget::Memory {
+            style = style.fg(Color::Cyan);
+        }
         let table = Table::new(rows, &widths)
-            .block(Block::default().borders(ratatui::widgets::Borders::ALL).border_type(BorderType::Rounded))
-            .widths(&widths);
+            .block(
+                Block::default()
+                    .borders(ratatui::widgets::Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .title(Span::styled(" Memory (m) chart ", style)),
+            )
+            .widths(widths);
 
         frame.render_widget(table, area);
     }
@@ -231,8 +445,8 @@ impl App {
             let columns = [
                 format_option(disk_info.name.clone()),
                 format_option(disk_info.mount_point.clone()),
-                format_option_units(disk_info.total_space.map(|v| v as f64)),
-                format_option_units(disk_info.available_space.map(|v| v as f64)),
+                format_option_units(disk_info.total_space.map(|v| v as f64), self.units),
+                format_option_units(disk_info.available_space.map(|v| v as f64), self.units),
             ];
             let cells = columns.iter().map(|content| Cell::from(Text::from(content.clone()))).collect::<Vec<_>>();
             Row::new(cells).height(ITEM_HEIGHT)
@@ -248,34 +462,486 @@ impl App {
         let table = Table::new(rows, &widths)
             .header(header)
             .block(Block::default().borders(ratatui::widgets::Borders::ALL).border_type(BorderType::Rounded))
-            .widths(&widths);
+            .widths(widths);
+
+        frame.render_widget(table, area);
+    }
+
+    // renderuje tablice obciążenia urządzeń blokowych
+    // analogiczna co render_disk_usage
+    pub fn render_block_devices(&mut self, frame: &mut Frame, area: Rect) {
+        let header_names = ["Device", "Read/s", "Write/s", "IOPS", "Await", "Busy %"];
+        let header_cells: Vec<Cell> = header_names.into_iter().map(Cell::from).collect();
+        let header = Row::new(header_cells).style(Style::default()).height(ITEM_HEIGHT);
+
+        let rows = self.general_info.block_device_tab.iter().map(|device| {
+            let columns = [
+                device.name.clone(),
+                format!("{}/s", change_units(device.read_rate, self.units)),
+                format!("{}/s", change_units(device.write_rate, self.units)),
+                format!("{:.0}", device.iops),
+                format_option(device.await_ms.map(|ms| format!("{ms:.1}ms"))),
+                format!("{:.1}", device.util),
+            ];
+            let mut row = Row::new(columns.map(Cell::from)).height(ITEM_HEIGHT);
+            // urządzenie aktualnie na wykresie
+            if matches!(&self.chart, ChartTarget::BlockDevice { name, .. } if *name == device.name) {
+                row = row.style(Style::default().fg(Color::Cyan));
+            }
+            row
+        }).collect::<Vec<_>>();
+
+        let table = Table::new(rows, BLOCK_DEVICE_TABLE_WIDTHS)
+            .header(header)
+            .block(
+                Block::default()
+                    .borders(ratatui::widgets::Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .title(" Disk I/O (b) chart "),
+            );
+
+        frame.render_widget(table, area);
+    }
+
+    // renderuje tablice presji (PSI) - paski avg10 oraz średnie z 10, 60 i 300 sekund
+    // analogiczna co render_disk_usage
+    pub fn render_pressure(&mut self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .borders(ratatui::widgets::Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title(" Pressure (p) chart ");
+        // jądro bez PSI (albo uruchomione z psi=0)
+        if self.general_info.pressure.is_empty() {
+            let message = Paragraph::new("PSI not available (/proc/pressure missing or disabled)").block(block);
+            frame.render_widget(message, area);
+            return;
+        }
+
+        let header_names = ["PSI", "Some avg10", "10s/60s/300s", "Full avg10", "10s/60s/300s", "Stalled"];
+        let header_cells: Vec<Cell> = header_names.into_iter().map(Cell::from).collect();
+        let header = Row::new(header_cells).style(Style::default()).height(ITEM_HEIGHT);
+
+        // pasek z kolorem zależnym od poziomu presji
+        let bar = |avg10: f64| {
+            let color = if avg10 >= 40.0 { Color::Red } else if avg10 >= 10.0 { Color::Yellow } else { Color::Green };
+            Cell::from(format!("{} {avg10:.1}", percent_bar(avg10, 8))).style(Style::default().fg(color))
+        };
+        let averages = |stall: &PressureStall| format!("{:.1} {:.1} {:.1}", stall.avg10, stall.avg60, stall.avg300);
+
+        let rows = self.general_info.pressure.iter().map(|(resource, pressure)| {
+            let cells = vec![
+                Cell::from(resource.name()),
+                bar(pressure.some.avg10),
+                Cell::from(averages(&pressure.some)),
+                pressure.full.map_or_else(|| Cell::from("--"), |full| bar(full.avg10)),
+                Cell::from(format_option(pressure.full.as_ref().map(averages))),
+                Cell::from(format_duration(pressure.some.total_us / 1_000_000)),
+            ];
+            let mut row = Row::new(cells).height(ITEM_HEIGHT);
+            // zasób aktualnie na wykresie
+            if self.chart == ChartTarget::Pressure(*resource) {
+                row = row.style(Style::default().add_modifier(Modifier::BOLD));
+            }
+            row
+        }).collect::<Vec<_>>();
+
+        let widths = [
+            Constraint::Length(6),
+            Constraint::Length(14),
+            Constraint::Fill(1),
+            Constraint::Length(14),
+            Constraint::Fill(1),
+            Constraint::Length(9),
+        ];
+
+        let table = Table::new(rows, widths).header(header).block(block);
 
         frame.render_widget(table, area);
     }
 
+    // renderuje tablice interfejsów sieciowych
+    // analogiczna co render_disk_usage
+    pub fn render_network_usage(&mut self, frame: &mut Frame, area: Rect) {
+        let header_names = ["Iface", "RX/s", "TX/s", "RX", "TX", "Pkts rx/tx", "Err rx/tx", "Drop rx/tx"];
+        let header_cells: Vec<Cell> = header_names.into_iter().map(Cell::from).collect();
+        let header = Row::new(header_cells).style(Style::default()).height(ITEM_HEIGHT);
+
+        let rows = self.general_info.network_tab.iter().map(|net| {
+            let columns = [
+                net.name.clone(),
+                format!("{}/s", change_units(net.rx_rate, self.units)),
+                format!("{}/s", change_units(net.tx_rate, self.units)),
+                change_units(net.total_rx as f64, self.units),
+                change_units(net.total_tx as f64, self.units),
+                format!("{}/{}", net.rx_packets, net.tx_packets),
+                format!("{}/{}", net.rx_errors, net.tx_errors),
+                format!("{}/{}", format_option(net.rx_dropped), format_option(net.tx_dropped)),
+            ];
+            let mut row = Row::new(columns.map(Cell::from)).height(ITEM_HEIGHT);
+            // interfejs aktualnie na wykresie
+            if self.chart == ChartTarget::Network(net.name.clone()) {
+                row = row.style(Style::default().fg(Color::Cyan));
+            }
+            row
+        }).collect::<Vec<_>>();
+
+        let widths = [
+            Constraint::Fill(2),
+            Constraint::Fill(2),
+            Constraint::Fill(2),
+            Constraint::Fill(2),
+            Constraint::Fill(2),
+            Constraint::Fill(3),
+            Constraint::Fill(3),
+            Constraint::Fill(3),
+        ];
+
+        let table = Table::new(rows, &widths)
+            .header(header)
+            .block(
+                Block::default()
+                    .borders(ratatui::widgets::Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .title(" Network (n) chart "),
+            )
+            .widths(widths);
+
+        frame.render_widget(table, area);
+    }
+
+    // okienko ze szczegółami wybranego procesu
+    pub fn render_details(&self, frame: &mut Frame, area: Rect, pid: Pid) {
+        let label = |name: &str, value: String| {
+            Line::from(vec![
+                Span::styled(format!("{name:<16}"), Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(value),
+            ])
+        };
+
+        let lines = match self.find_process(pid) {
+            Some(proc) => {
+                let command = proc.command_line();
+                vec![
+                    label("PID", proc.pid.to_string()),
+                    label("Name", proc.name.clone()),
+                    label("User", format_option(proc.user_label())),
+                    label("Parent PID", format_option(proc.parent)),
+                    label("Session", format_option(proc.session)),
+                    label("Terminal", format_option(proc.tty.clone())),
+                    label("Status", proc.status.clone()),
+                    label("Started", format_timestamp(proc.start_time)),
+                    label("Running for", format_duration(proc.run_time)),
+                    label(
+                        "Memory",
+                        [
+                            ProcessName::MEM,
+                            ProcessName::PSS,
+                            ProcessName::SHARED,
+                            ProcessName::ANON,
+                            ProcessName::SWAP,
+                            ProcessName::VIRT,
+                        ]
+                        .map(|col| format!("{} {}", col.label(), self.column_text(proc, col)))
+                        .join(", "),
+                    ),
+                    label("Executable", format_option(proc.exe.clone())),
+                    label("Working dir", format_option(self.details_cwd.clone())),
+                    label("Command line", if command.is_empty() { "--".to_string() } else { command }),
+                ]
+            }
+            None => vec![Line::from(format!("Process {pid} has exited"))],
+        };
+
+        let popup = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(
+                Block::bordered()
+                    .border_type(BorderType::Rounded)
+                    .title(format!(" Process {pid} "))
+                    .title_bottom(" (Esc/Enter) close "),
+            );
+        let area = centered_rect(70, 60, area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(popup, area);
+    }
+
+    // okienko z informacjami o procesorze - model, pamięci podręczne, topologia
+    // i tabela częstotliwości pogrupowana po rdzeniach fizycznych
+    pub fn render_cpu_info(&self, frame: &mut Frame, area: Rect) {
+        let cpus = &self.general_info.cpu_usage_tab;
+        let model = &self.general_info.cpu_model;
+        let label = |name: &str, value: String| {
+            Line::from(vec![
+                Span::styled(format!("{name:<16}"), Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(value),
+            ])
+        };
+
+        let sockets = cpus.iter().filter_map(|cpu| cpu.topology.map(|t| t.package)).collect::<HashSet<_>>().len();
+        let cores = cpus.iter().filter_map(|cpu| cpu.topology).collect::<HashSet<_>>().len();
+        let threads = cpus.len();
+        let caches = model.caches.iter().map(|cache| format!("{} {}", cache.name, cache.size)).collect::<Vec<_>>();
+        let mut lines = vec![
+            label("Model", format_option(model.name.clone())),
+            label("Caches", if caches.is_empty() { "--".to_string() } else { caches.join(", ") }),
+        ];
+        if cores > 0 {
+            lines.push(label("Topology", format!("{sockets} socket(s), {cores} physical core(s), {threads} thread(s)")));
+        } else {
+            lines.push(label("Topology", format!("{threads} logical CPU(s), no topology in /sys/devices/system/cpu")));
+        }
+        // jak liczony jest procent CPU procesów (zob. CpuMode::normalize)
+        let max = match self.cpu_mode {
+            CpuMode::Irix => threads * 100,
+            CpuMode::Solaris => 100,
+        };
+        let explanation = match self.cpu_mode {
+            CpuMode::Irix => "100% per logical CPU",
+            CpuMode::Solaris => "share of all logical CPUs",
+        };
+        lines.push(label(
+            "Process CPU %",
+            format!("{} mode ({explanation}) - up to {max}% with all {threads} thread(s) busy, (I) to switch", self.cpu_mode.name()),
+        ));
+
+        let header = Row::new(["Core", "CPU", "Usage %", "Freq", "Min", "Max", "Governor"].map(Cell::from))
+            .style(Style::default().add_modifier(Modifier::BOLD))
+            .height(ITEM_HEIGHT);
+        let order = self.cpu_row_order();
+        let frequency = |khz: Option<u64>| format_option(khz.map(format_frequency));
+        let rows = order.iter().enumerate().map(|(row, &i)| {
+            let cpu = &cpus[i];
+            // rdzeń fizyczny podany tylko przy pierwszym z jego wątków
+            let first = row == 0 || cpus[order[row - 1]].topology != cpu.topology;
+            let core = match cpu.topology {
+                Some(topology) if first => format!("S{} C{}", topology.package, topology.core),
+                Some(_) => String::new(),
+                None => "--".to_string(),
+            };
+            Row::new([
+                core,
+                cpu.name.clone(),
+                format_option(cpu.usage.map(|v| format!("{v:.1}"))),
+                frequency(cpu.frequency.current),
+                frequency(cpu.frequency.min),
+                frequency(cpu.frequency.max),
+                format_option(cpu.frequency.governor.clone()),
+            ].map(Cell::from)).height(ITEM_HEIGHT)
+        }).collect::<Vec<_>>();
+
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title(" CPU ")
+            .title_bottom(" (Esc/c) close ");
+        let area = centered_rect(70, 70, area);
+        let inner = block.inner(area);
+        let [summary, table_area] = Layout::vertical([Constraint::Length(lines.len() as u16 + 1), Constraint::Fill(1)]).areas(inner);
+        let widths = [
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(9),
+            Constraint::Length(9),
+            Constraint::Length(9),
+            Constraint::Fill(1),
+        ];
+        let table = Table::new(rows, widths).header(header);
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), summary);
+        frame.render_widget(table, table_area);
+    }
+
+    // okienko wyboru sygnału dla procesu
+    pub fn render_signal_picker(&self, frame: &mut Frame, area: Rect, pid: Pid, selected: usize, tree: bool) {
+        let mut lines: Vec<Line> = SignalKind::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, signal)| {
+                if i == selected {
+                    Line::styled(format!(">> {}", signal.name()), Style::default().add_modifier(Modifier::REVERSED))
+                } else {
+                    Line::raw(format!("   {}", signal.name()))
+                }
+            })
+            .collect();
+        lines.push(Line::raw(""));
+        lines.push(Line::raw(format!("[{}] (t) whole process tree", if tree { "x" } else { " " })));
+
+        let popup = Paragraph::new(lines).block(
+            Block::bordered()
+                .border_type(BorderType::Rounded)
+                .title(format!(" Send signal to {pid} "))
+                .title_bottom(" (Enter) choose | (Esc) cancel "),
+        );
+        let area = centered_rect(30, 40, area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(popup, area);
+    }
+
+    // okienko potwierdzenia wysłania sygnału
+    pub fn render_confirm_signal(&self, frame: &mut Frame, area: Rect, targets: &[ProcessKey], signal: SignalKind, tree: bool) {
+        let Some(pid) = targets.first().map(|target| target.pid) else {
+            return;
+        };
+        let name = self.find_process(pid).map(|proc| proc.name.clone()).unwrap_or_default();
+        let target = if tree {
+            let children = targets.len() - 1;
+            format!("{pid} ({name}) and {children} descendant process(es)")
+        } else {
+            format!("{pid} ({name})")
+        };
+        let popup = Paragraph::new(vec![
+            Line::raw(format!("Send {} to {target}?", signal.name())),
+            Line::raw(""),
+            Line::styled("(y) yes | (n) no", Style::default().add_modifier(Modifier::BOLD)),
+        ])
+        .centered()
+        .wrap(Wrap { trim: true })
+        .block(Block::bordered().border_type(BorderType::Double).title(" Confirm "));
+        let area = centered_rect(40, 20, area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(popup, area);
+    }
+
+    // okienko wyboru widocznych kolumn i ich kolejności
+    pub fn render_column_picker(&self, frame: &mut Frame, area: Rect, selected: usize) {
+        let lines: Vec<Line> = self
+            .column_picker_order()
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                let mark = if self.columns.contains(column) { "x" } else { " " };
+                let text = format!("[{mark}] {:<13} {}", column.field_name(), column.label());
+                if i == selected {
+                    Line::styled(format!(">> {text}"), Style::default().add_modifier(Modifier::REVERSED))
+                } else {
+                    Line::raw(format!("   {text}"))
+                }
+            })
+            .collect();
+
+        let popup = Paragraph::new(lines).block(
+            Block::bordered()
+                .border_type(BorderType::Rounded)
+                .title(" Columns (Space) show/hide | (Shift + ↑/↓) move ")
+                .title_bottom(" (Enter/Esc) close and save "),
+        );
+        let area = centered_rect(40, 60, area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(popup, area);
+    }
+
     // centralna funkcja do rysowania
     pub fn draw(&mut self, frame: &mut Frame) {
-        // dzielenie przestrzeni na główną i opis na dole
-        let vertical: [Rect; 2] = Layout::default()
-            .direction(ratatui::layout::Direction::Vertical)
-            .constraints([Constraint::Min(5), Constraint::Length(4)])
-            .areas(frame.area());
+        let panels = self.panels;
+        // okienko filtra zajmuje miejsce opisu, więc pokazuję je nawet przy ukrytym opisie
+        let show_footer = panels.footer || self.mode == Mode::FilterPrompt;
+        let show_performance = panels.cpu || panels.memory || panels.pressure || panels.disks || panels.io || panels.network;
+        // dzielenie przestrzeni na nagłówek, główną i opis na dole
+        let footer_height = INFO_TEXT.len() as u16 + 2;
+        let [header, main, footer] = split_visible(
+            frame.area(),
+            true,
+            [(panels.header, Constraint::Length(1)), (true, Constraint::Min(5)), (show_footer, Constraint::Length(footer_height))],
+        );
         // dzielenie na główną tabele po lewej i reszte po prawej
-        let [left, right]: [Rect; 2] = Layout::horizontal([Constraint::Fill(1); 2]).areas(vertical[0]);
-        // dzielenie na wykres i reszte danych
-        let [line_chart, performance]: [Rect; 2] = Layout::vertical([Constraint::Fill(1); 2]).areas(right);
+        let [left, right] = split_visible(main, false, [(true, Constraint::Fill(1)), (panels.chart || panels.heatmap || show_performance, Constraint::Fill(1))]);
+        // dzielenie na wykres, mapę cieplną (wiersz na rdzeń, najwyżej trzecia część wysokości) i reszte danych
+        // (tabel jest więcej, więc dostają więcej miejsca)
+        let heatmap_height = (self.core_history.len() as u16 + 2).min(right.height / 3);
+        let [line_chart, heatmap, performance] = split_visible(
+            right,
+            true,
+            [
+                (panels.chart, Constraint::Fill(2)),
+                (panels.heatmap, Constraint::Length(heatmap_height)),
+                (show_performance, Constraint::Fill(3)),
+            ],
+        );
         // dzielenie na tabele cpu i reszte
-        let [cpu_rect, mem_rect]: [Rect; 2] = Layout::horizontal([Constraint::Fill(1), Constraint::Fill(3)]).areas(performance);
-        // ostatnie dzielenie na tabele dal ramu i tabele dla dysku
-        let [ram_rect, disk_rect]: [Rect; 2] = Layout::vertical([Constraint::Fill(1); 2]).areas(mem_rect);
+        let [cpu_rect, mem_rect] =
+            split_visible(performance, false, [(panels.cpu, Constraint::Fill(1)), (panels.memory || panels.pressure || panels.disks || panels.io || panels.network, Constraint::Fill(3))]);
+        // ostatnie dzielenie na tabele dal ramu, presji, tabele dla dysku, obciążenia dysków i sieci
+        // presja ma wiersz na każdy zasób (albo jeden na komunikat o braku PSI)
+        let pressure_height = self.general_info.pressure.len().max(1) as u16 + 3;
+        let [ram_rect, pressure_rect, disk_rect, io_rect, network_rect] = split_visible(
+            mem_rect,
+            true,
+            [
+                (panels.memory, Constraint::Length(MEMORY_PANEL_HEIGHT)),
+                (panels.pressure, Constraint::Length(pressure_height)),
+                (panels.disks, Constraint::Fill(1)),
+                (panels.io, Constraint::Fill(1)),
+                (panels.network, Constraint::Fill(1)),
+            ],
+        );
+
+        // zapamiętanie obszarów klikalnych tabel (obsługa myszy)
+        self.table_area = left;
+        self.cpu_table_area = cpu_rect;
+        self.disk_table_area = disk_rect;
+        self.memory_table_area = ram_rect;
+        self.pressure_table_area = pressure_rect;
+        self.network_table_area = network_rect;
+        self.block_device_table_area = io_rect;
 
         // renderowanie widgetow
+        if panels.header {
+            self.render_header(frame, header);
+        }
         self.render_table(frame, left);
         self.render_scrollbar(frame, left);
-        self.render_footer(frame, vertical[1]);
-        self.render_animated_chart(frame, line_chart);
-        self.render_cpu_usage(frame, cpu_rect);
-        self.render_ram_usage(frame, ram_rect);
-        self.render_disk_usage(frame, disk_rect);
+        if self.mode == Mode::FilterPrompt {
+            self.render_filter_prompt(frame, footer);
+        } else if panels.footer {
+            self.render_footer(frame, footer);
+        }
+        if panels.chart {
+            self.render_animated_chart(frame, line_chart);
+        }
+        if panels.heatmap {
+            self.render_heatmap(frame, heatmap);
+        }
+        if panels.cpu {
+            self.render_cpu_usage(frame, cpu_rect);
+        }
+        if panels.memory {
+            self.render_ram_usage(frame, ram_rect);
+        }
+        if panels.pressure {
+            self.render_pressure(frame, pressure_rect);
+        }
+        if panels.disks {
+            self.render_disk_usage(frame, disk_rect);
+        }
+        if panels.io {
+            self.render_block_devices(frame, io_rect);
+        }
+        if panels.network {
+            self.render_network_usage(frame, network_rect);
+        }
+
+        // okienka nakładane na resztę interfejsu
+        match &self.mode {
+            Mode::Normal | Mode::Search | Mode::FilterPrompt => {}
+            Mode::Details(pid) => self.render_details(frame, frame.area(), *pid),
+            Mode::SignalPicker { target, selected, tree } => {
+                self.render_signal_picker(frame, frame.area(), target.pid, *selected, *tree)
+            }
+            Mode::ConfirmSignal { targets, signal, tree } => {
+                self.render_confirm_signal(frame, frame.area(), targets, *signal, *tree)
+            }
+            Mode::ColumnPicker { selected } => self.render_column_picker(frame, frame.area(), *selected),
+            Mode::CpuInfo => self.render_cpu_info(frame, frame.area()),
+        }
+
+        // bez kolorów zostawiam tylko pogrubienia i odwrócenia (zaznaczenie dalej widać)
+        if !self.color {
+            for cell in frame.buffer_mut().content.iter_mut() {
+                cell.set_fg(Color::Reset).set_bg(Color::Reset);
+            }
+        }
     }
 } 
\ No newline at end of file
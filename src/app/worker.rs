@@ -0,0 +1,55 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::models::{DataSource, Snapshot};
+
+// uchwyt do wątku zbierającego dane w tle
+pub struct CollectorHandle {
+    receiver: Receiver<Snapshot>, // kanał z kolejnymi paczkami danych
+    paused: Arc<AtomicBool>, // współdzielona flaga zatrzymania (app.stop)
+}
+
+impl CollectorHandle {
+    // uruchomienie wątku, który co `interval` zbiera dane i wysyła je kanałem
+    pub fn spawn<S: DataSource + Send + 'static>(mut source: S, interval: Duration) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let paused = Arc::new(AtomicBool::new(false));
+        let worker_paused = Arc::clone(&paused);
+
+        thread::spawn(move || {
+            // pomiary według stałego harmonogramu - czas zbierania danych nie przesuwa kolejnych odczytów
+            let mut next_tick = Instant::now() + interval;
+            loop {
+                thread::sleep(next_tick.saturating_duration_since(Instant::now()));
+                next_tick += interval;
+                // zbieranie trwało dłużej niż odstęp - pominięte odczyty przepadają zamiast nadrabiania seriami
+                let now = Instant::now();
+                if next_tick < now {
+                    next_tick = now + interval;
+                }
+                if worker_paused.load(Ordering::Relaxed) {
+                    continue;
+                }
+                // UI zakończyło działanie - kończymy też wątek
+                if sender.send(source.collect()).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self { receiver, paused }
+    }
+
+    // przekazanie stanu zatrzymania do wątku
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::Relaxed);
+    }
+
+    // najświeższa paczka danych (starsze, nieodebrane są pomijane)
+    pub fn try_latest(&self) -> Option<Snapshot> {
+        self.receiver.try_iter().last()
+    }
+}
@@ -0,0 +1,92 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use crate::config::Config;
+
+// argumenty wiersza poleceń
+#[derive(Parser, Debug)]
+#[command(version, about = "Terminal system monitor")]
+pub struct Cli {
+    /// Refresh interval in milliseconds
+    #[arg(short, long, value_name = "MS")]
+    pub interval: Option<u64>,
+
+    /// Column to sort by (pid, name, cpu, mem, read, write, user, ...)
+    #[arg(short, long, value_name = "COLUMN")]
+    pub sort: Option<String>,
+
+    /// Reverse the sort direction (descending by default)
+    #[arg(short, long)]
+    pub reverse: bool,
+
+    /// Show only processes matching an expression, e.g. `cpu > 5 and mem > 200MB and user == "ci"`
+    #[arg(long, value_name = "EXPR")]
+    pub filter: Option<String>,
+
+    /// Show only processes owned by a user
+    #[arg(short, long, value_name = "NAME")]
+    pub user: Option<String>,
+
+    /// Start with a process selected and charted
+    #[arg(short, long, value_name = "PID")]
+    pub pid: Option<u32>,
+
+    /// How process CPU % is counted: `irix` (100% per logical CPU) or `solaris` (share of the whole machine)
+    #[arg(long, value_name = "MODE")]
+    pub cpu_mode: Option<String>,
+
+    /// Config file to use instead of `$XDG_CONFIG_HOME/rust-system-monitor/config.toml`
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Override a config file setting for this run, e.g. `--set interval=1000 --set units=decimal`
+    #[arg(long = "set", value_name = "KEY=VALUE")]
+    pub set: Vec<String>,
+
+    /// Disable colors (also when the NO_COLOR environment variable is set)
+    #[arg(long)]
+    pub no_color: bool,
+
+    /// Append every collected sample to a file (JSON lines) for a later `--replay`
+    #[arg(long, value_name = "PATH", conflicts_with = "replay")]
+    pub record: Option<PathBuf>,
+
+    /// Replay samples recorded with `--record` instead of reading the live system
+    #[arg(long, value_name = "PATH")]
+    pub replay: Option<PathBuf>,
+}
+
+impl Cli {
+    // przeniesienie opcji, które mają odpowiednik w pliku konfiguracyjnym (mają pierwszeństwo)
+    pub fn apply(&self, config: &mut Config) {
+        if let Some(interval) = self.interval {
+            config.interval = Some(interval);
+        }
+        if let Some(sort) = &self.sort {
+            config.sort = Some(sort.clone());
+        }
+        if let Some(mode) = &self.cpu_mode {
+            config.cpu_mode = Some(mode.clone());
+        }
+    }
+
+    // wyrażenie filtrujące z `--filter` i `--user`
+    pub fn filter_expression(&self) -> Option<String> {
+        let user = self.user.as_ref().map(|user| {
+            // nazwę biorę w cudzysłów, którego w niej nie ma
+            let quote = if user.contains('"') { '\'' } else { '"' };
+            format!("user == {quote}{user}{quote}")
+        });
+        match (&self.filter, user) {
+            (Some(filter), Some(user)) => Some(format!("({filter}) and {user}")),
+            (Some(filter), None) => Some(filter.clone()),
+            (None, user) => user,
+        }
+    }
+
+    // czy używać kolorów
+    pub fn color(&self) -> bool {
+        !self.no_color && std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+    }
+}
@@ -0,0 +1,332 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use sysinfo::Pid;
+
+use color_eyre::{Result, eyre::{WrapErr, eyre}};
+use serde::{Deserialize, Serialize};
+
+use crate::app::ChartTarget;
+use crate::models::{PressureResource, ProcessName, DEFAULT_HISTORY_LEN, DEFAULT_HISTORY_RETENTION, DEFAULT_INTERVAL};
+use crate::utils::{CpuMode, Units};
+
+// katalog z konfiguracją w $XDG_CONFIG_HOME (lub ~/.config)
+const CONFIG_DIR: &str = "rust-system-monitor";
+const CONFIG_FILE: &str = "config.toml";
+
+// dopuszczalne zakresy ustawień - przy krótszym odstępie sysinfo nie policzy zużycia CPU
+const INTERVAL_RANGE: (u64, u64) = (sysinfo::MINIMUM_CPU_UPDATE_INTERVAL.as_millis() as u64, 60_000);
+const HISTORY_RANGE: (usize, usize) = (2, 3600);
+const RETENTION_RANGE: (u64, u64) = (0, 86_400);
+
+// nazwy paneli, które można ukryć
+const PANEL_NAMES: [&str; 10] = ["header", "chart", "heatmap", "cpu", "memory", "pressure", "disks", "io", "network", "footer"];
+
+// zawartość pliku konfiguracyjnego (wszystkie pola opcjonalne)
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interval: Option<u64>, // odświeżanie w milisekundach
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub history: Option<usize>, // liczba zapamiętanych pomiarów (długość wykresu)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub history_retention: Option<u64>, // ile sekund trzymać historię zakończonego procesu
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<String>, // kolumna sortowania
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_order: Option<String>, // "asc" albo "desc"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chart: Option<String>, // początkowy wykres: "cpu", "memory", "core:N", "pressure:<zasób>" albo "disk:<punkt montowania>"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub panels: Option<Vec<String>>, // widoczne panele
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub units: Option<String>, // "binary" (1024) albo "decimal" (1000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_mode: Option<String>, // "irix" (100% na procesor logiczny) albo "solaris" (cała maszyna to 100%)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub columns: Option<Vec<String>>, // widoczne kolumny tabeli procesów, w kolejności
+}
+
+// widoczność paneli interfejsu
+#[derive(Debug, Clone, Copy)]
+pub struct Panels {
+    pub header: bool,
+    pub chart: bool,
+    pub heatmap: bool,
+    pub cpu: bool,
+    pub memory: bool,
+    pub pressure: bool,
+    pub disks: bool,
+    pub io: bool,
+    pub network: bool,
+    pub footer: bool,
+}
+
+impl Default for Panels {
+    fn default() -> Self {
+        Self { header: true, chart: true, heatmap: true, cpu: true, memory: true, pressure: true, disks: true, io: true, network: true, footer: true }
+    }
+}
+
+// ustawienia po sprawdzeniu i uzupełnieniu wartościami domyślnymi
+#[derive(Debug, Clone)]
+pub struct Settings {
+    pub interval: Duration,
+    pub history_len: usize,
+    pub history_retention: Duration,
+    pub sort: ProcessName,
+    pub reverse_sort: bool,
+    pub chart: ChartTarget,
+    pub panels: Panels,
+    pub units: Units,
+    pub cpu_mode: CpuMode,
+    pub columns: Vec<ProcessName>,
+    pub filter: Option<String>, // początkowy filtr (tylko z wiersza poleceń)
+    pub focus_pid: Option<Pid>, // proces zaznaczony na starcie (tylko z wiersza poleceń)
+    pub color: bool, // czy używać kolorów
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_millis(DEFAULT_INTERVAL),
+            history_len: DEFAULT_HISTORY_LEN,
+            history_retention: Duration::from_secs(DEFAULT_HISTORY_RETENTION),
+            sort: ProcessName::CPU,
+            reverse_sort: true,
+            chart: ChartTarget::CpuAvg,
+            panels: Panels::default(),
+            units: Units::Binary,
+            cpu_mode: CpuMode::Solaris,
+            columns: ProcessName::DEFAULT_COLUMNS.to_vec(),
+            filter: None,
+            focus_pid: None,
+            color: true,
+        }
+    }
+}
+
+impl Config {
+    // domyślna ścieżka do pliku konfiguracyjnego
+    pub fn default_path() -> Option<PathBuf> {
+        let base = std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+        Some(base.join(CONFIG_DIR).join(CONFIG_FILE))
+    }
+
+    // wczytanie konfiguracji z nadpisaniem pojedynczych kluczy (`--set klucz=wartość`)
+    pub fn load_with_overrides(path: Option<&Path>, overrides: &[String]) -> Result<Config> {
+        let mut table = toml::Table::new();
+        if let Some(path) = path {
+            let content = match fs::read_to_string(path) {
+                Ok(content) => content,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+                Err(err) => return Err(err).wrap_err_with(|| format!("cannot read config file {}", path.display())),
+            };
+            table = toml::from_str(&content).wrap_err_with(|| format!("invalid config file {}", path.display()))?;
+            // sprawdzenie już przy wczytaniu, żeby błąd wskazał plik
+            Self::from_table(table.clone())
+                .and_then(|config| config.settings())
+                .wrap_err_with(|| format!("invalid config file {}", path.display()))?;
+        }
+
+        if overrides.is_empty() {
+            return Self::from_table(table);
+        }
+        for item in overrides {
+            let (key, value) = parse_override(item)?;
+            table.insert(key, value);
+        }
+        let config = Self::from_table(table).wrap_err("invalid --set option")?;
+        config.settings().wrap_err("invalid --set option")?;
+        Ok(config)
+    }
+
+    // zamiana tablicy TOML na konfigurację
+    fn from_table(table: toml::Table) -> Result<Config> {
+        Ok(toml::Value::Table(table).try_into()?)
+    }
+
+    // sprawdzenie wszystkich pól i uzupełnienie brakujących wartościami domyślnymi
+    pub fn settings(&self) -> Result<Settings> {
+        let defaults = Settings::default();
+        let interval = match self.interval {
+            Some(ms) => Duration::from_millis(in_range("interval", ms, INTERVAL_RANGE)?),
+            None => defaults.interval,
+        };
+        let history_len = match self.history {
+            Some(len) => in_range("history", len, HISTORY_RANGE)?,
+            None => defaults.history_len,
+        };
+        let history_retention = match self.history_retention {
+            Some(secs) => Duration::from_secs(in_range("history_retention", secs, RETENTION_RANGE)?),
+            None => defaults.history_retention,
+        };
+        let sort = match &self.sort {
+            Some(name) => parse_column("sort", name)?,
+            None => defaults.sort,
+        };
+        let reverse_sort = match self.sort_order.as_deref() {
+            None => defaults.reverse_sort,
+            Some("desc") => true,
+            Some("asc") => false,
+            Some(other) => return Err(eyre!("invalid `sort_order` \"{other}\" (expected \"asc\" or \"desc\")")),
+        };
+        let chart = match &self.chart {
+            Some(chart) => parse_chart(chart)?,
+            None => defaults.chart,
+        };
+        let panels = match &self.panels {
+            Some(names) => parse_panels(names)?,
+            None => defaults.panels,
+        };
+        let units = match self.units.as_deref() {
+            None => defaults.units,
+            Some("binary") => Units::Binary,
+            Some("decimal") => Units::Decimal,
+            Some(other) => return Err(eyre!("invalid `units` \"{other}\" (expected \"binary\" or \"decimal\")")),
+        };
+        let cpu_mode = match self.cpu_mode.as_deref() {
+            None => defaults.cpu_mode,
+            Some("irix") => CpuMode::Irix,
+            Some("solaris") => CpuMode::Solaris,
+            Some(other) => return Err(eyre!("invalid `cpu_mode` \"{other}\" (expected \"irix\" or \"solaris\")")),
+        };
+        Ok(Settings {
+            interval,
+            history_len,
+            history_retention,
+            sort,
+            reverse_sort,
+            chart,
+            panels,
+            units,
+            cpu_mode,
+            columns: self.columns()?,
+            ..defaults
+        })
+    }
+
+    // lista kolumn z konfiguracji (lub domyślna)
+    pub fn columns(&self) -> Result<Vec<ProcessName>> {
+        let Some(names) = &self.columns else {
+            return Ok(ProcessName::DEFAULT_COLUMNS.to_vec());
+        };
+        let mut columns = Vec::new();
+        for name in names {
+            let column = parse_column("columns", name)?;
+            if columns.contains(&column) {
+                return Err(eyre!("column `{name}` listed more than once in `columns`"));
+            }
+            columns.push(column);
+        }
+        if columns.is_empty() {
+            return Err(eyre!("`columns` must list at least one column"));
+        }
+        Ok(columns)
+    }
+
+    // zapisanie wybranych kolumn - zmieniam tylko klucz `columns`, reszta pliku
+    // (komentarze, formatowanie, pozostałe klucze) zostaje taka, jaka była na dysku
+    pub fn save_columns(path: &Path, columns: &[ProcessName]) -> Result<()> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err).wrap_err_with(|| format!("cannot read config file {}", path.display())),
+        };
+        let mut document: toml_edit::DocumentMut =
+            content.parse().wrap_err_with(|| format!("invalid config file {}", path.display()))?;
+        let mut names: toml_edit::Array = columns.iter().map(|c| c.field_name()).collect();
+        // komentarz za starą listą zostaje na swoim miejscu
+        if let Some(old) = document.get("columns").and_then(|item| item.as_value()) {
+            *names.decor_mut() = old.decor().clone();
+        }
+        document["columns"] = toml_edit::value(names);
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).wrap_err_with(|| format!("cannot create {}", dir.display()))?;
+        }
+        fs::write(path, document.to_string()).wrap_err_with(|| format!("cannot write config file {}", path.display()))
+    }
+}
+
+// sprawdzenie, czy liczba mieści się w zakresie
+fn in_range<T: PartialOrd + std::fmt::Display + Copy>(key: &str, value: T, (min, max): (T, T)) -> Result<T> {
+    if value < min || value > max {
+        return Err(eyre!("`{key}` must be between {min} and {max}, got {value}"));
+    }
+    Ok(value)
+}
+
+// nazwa kolumny (jak w filtrach)
+fn parse_column(key: &str, name: &str) -> Result<ProcessName> {
+    ProcessName::from_field_name(name).ok_or_else(|| {
+        let known: Vec<&str> = ProcessName::ALL.iter().map(|c| c.field_name()).collect();
+        eyre!("unknown column `{name}` in `{key}` (expected one of: {})", known.join(", "))
+    })
+}
+
+// początkowy wykres: "cpu", "memory", "core:N", "pressure:<zasób>" albo "disk:<punkt montowania>"
+fn parse_chart(chart: &str) -> Result<ChartTarget> {
+    if chart == "cpu" {
+        return Ok(ChartTarget::CpuAvg);
+    }
+    if chart == "memory" {
+        return Ok(ChartTarget::Memory);
+    }
+    if let Some(core) = chart.strip_prefix("core:") {
+        let core = core.parse().map_err(|_| eyre!("invalid core number in `chart` \"{chart}\""))?;
+        return Ok(ChartTarget::Core(core));
+    }
+    if let Some(resource) = chart.strip_prefix("pressure:") {
+        let resource = PressureResource::from_name(resource)
+            .ok_or_else(|| eyre!("invalid pressure resource in `chart` \"{chart}\" (expected cpu, memory or io)"))?;
+        return Ok(ChartTarget::Pressure(resource));
+    }
+    if let Some(mount) = chart.strip_prefix("disk:")
+        && !mount.is_empty()
+    {
+        return Ok(ChartTarget::Disk(mount.to_string()));
+    }
+    Err(eyre!("invalid `chart` \"{chart}\" (expected \"cpu\", \"memory\", \"core:N\", \"pressure:<resource>\" or \"disk:<mount point>\")"))
+}
+
+// lista widocznych paneli
+fn parse_panels(names: &[String]) -> Result<Panels> {
+    let mut panels = Panels { header: false, chart: false, heatmap: false, cpu: false, memory: false, pressure: false, disks: false, io: false, network: false, footer: false };
+    for name in names {
+        let panel = match name.as_str() {
+            "header" => &mut panels.header,
+            "chart" => &mut panels.chart,
+            "heatmap" => &mut panels.heatmap,
+            "cpu" => &mut panels.cpu,
+            "memory" => &mut panels.memory,
+            "pressure" => &mut panels.pressure,
+            "disks" => &mut panels.disks,
+            "io" => &mut panels.io,
+            "network" => &mut panels.network,
+            "footer" => &mut panels.footer,
+            _ => return Err(eyre!("unknown panel `{name}` in `panels` (expected one of: {})", PANEL_NAMES.join(", "))),
+        };
+        *panel = true;
+    }
+    Ok(panels)
+}
+
+// `klucz=wartość` - wartość w składni TOML, a gdy się nie da, traktowana jako napis
+fn parse_override(item: &str) -> Result<(String, toml::Value)> {
+    let (key, value) = item
+        .split_once('=')
+        .ok_or_else(|| eyre!("invalid --set option `{item}` (expected KEY=VALUE)"))?;
+    let key = key.trim().to_string();
+    let value = value.trim();
+    let value = match toml::from_str::<toml::Table>(&format!("value = {value}")) {
+        Ok(mut table) => table.remove("value").unwrap_or_else(|| toml::Value::String(value.to_string())),
+        Err(_) => toml::Value::String(value.to_string()),
+    };
+    Ok((key, value))
+}
@@ -1,18 +1,70 @@
-use color_eyre::Result;
+use clap::Parser;
+use color_eyre::{Result, eyre::{WrapErr, eyre}};
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::execute;
+use std::io::stdout;
+use std::path::PathBuf;
+use sysinfo::Pid;
 
 mod app;
+mod cli;
+mod config;
 mod models;
 mod utils;
 
 use app::{App, run};
+use cli::Cli;
+use config::{Config, Settings};
+use models::{Collector, DataSource, RecordingSource, ScriptedSource};
 
 fn main() -> Result<()> {
     color_eyre::install()?;
+    let cli = Cli::parse();
+    // błędy w konfiguracji zgłaszam przed uruchomieniem interfejsu
+    if let Some(path) = &cli.config
+        && !path.exists()
+    {
+        return Err(eyre!("config file {} does not exist", path.display()));
+    }
+    let config_path = cli.config.clone().or_else(Config::default_path);
+    let mut config = Config::load_with_overrides(config_path.as_deref(), &cli.set)?;
+    cli.apply(&mut config);
+    let mut settings = config.settings().wrap_err("invalid command-line option")?;
+    settings.reverse_sort ^= cli.reverse;
+    settings.filter = cli.filter_expression();
+    settings.focus_pid = cli.pid.map(Pid::from_u32);
+    settings.color = cli.color();
+    match (&cli.replay, &cli.record) {
+        (Some(path), _) => start(ScriptedSource::load(path)?, &settings, config_path),
+        (None, Some(path)) => start(RecordingSource::create(Collector::new(), path)?, &settings, config_path),
+        (None, None) => start(Collector::new(), &settings, config_path),
+    }
+}
+
+// uruchomienie interfejsu z danymi z wybranego źródła (system, nagrywanie albo odtwarzanie nagrania)
+fn start(mut source: impl DataSource + Send + 'static, settings: &Settings, config_path: Option<PathBuf>) -> Result<()> {
+    // pierwsze dane pobieram synchronicznie, kolejne zbiera już wątek w tle
+    let snapshot = source.collect();
+    if let Some(pid) = settings.focus_pid
+        && !snapshot.0.iter().any(|proc| proc.pid == pid)
+    {
+        return Err(eyre!("no process with PID {pid}"));
+    }
+    let mut app = App::new(snapshot, settings);
+    app.config_path = config_path;
     let terminal = ratatui::init();
-    let app = App::new();
-    
-    let result = run(terminal, app);
-    
+    // hook z ratatui::init przywraca terminal przy panice, ale nie wie o myszy
+    let restore_terminal = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = execute!(stdout(), DisableMouseCapture);
+        restore_terminal(info);
+    }));
+    // przechwytywanie myszy (działa też w tmux z włączonym `mouse on`)
+    let result = execute!(stdout(), EnableMouseCapture)
+        .wrap_err("failed to enable mouse capture")
+        .and_then(|()| run(terminal, app, source));
+
+    let _ = execute!(stdout(), DisableMouseCapture);
     ratatui::restore();
     result
 }
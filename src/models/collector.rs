@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+use std::time::Instant;
+use sysinfo::{CpuRefreshKind, ThreadKind, Disks, Networks, ProcessRefreshKind, System, Uid, UpdateKind, Users};
+
+use super::{read_cpu_current_frequency, read_cpu_frequency, CpuFrequency, read_pss, read_status_memory, read_tty, StatusMemory, ProcessKey, read_cpu_model, read_cpu_topology, read_cpu_times, CpuModel, CpuTopology, read_diskstats, BlockDeviceInfo, CpuBreakdown, CpuTimes, DataSource, DiskStats, GeneralInfo, ProcessInfo};
+
+// co ile odświeżeń aktualizujemy zajętość dysków
+const DISK_REFRESH_EVERY: u32 = 4;
+// co ile odświeżeń przeładowujemy całą listę dysków (nowe punkty montowania)
+const DISK_LIST_REFRESH_EVERY: u32 = 20;
+// co ile odświeżeń przeładowujemy listę interfejsów sieciowych
+const NETWORK_LIST_REFRESH_EVERY: u32 = 20;
+// co ile odświeżeń przeładowujemy tablicę użytkowników
+const USERS_REFRESH_EVERY: u32 = 20;
+// co ile odświeżeń czytamy zakres częstotliwości i gubernatora procesorów (bieżąca częstotliwość - co odświeżenie)
+const CPU_FREQUENCY_REFRESH_EVERY: u32 = 20;
+// co ile odświeżeń czytamy rozbicie pamięci wszystkich procesów (status i kosztowny smaps_rollup),
+// w międzyczasie tylko dla nowych procesów
+const MEMORY_REFRESH_EVERY: u32 = 10;
+
+// rozbicie pamięci procesu z ostatniego odczytu
+#[derive(Debug, Clone, Copy)]
+struct ProcessMemory {
+    status: Option<StatusMemory>,
+    pss: Option<u64>,
+}
+
+// kolektor danych - trzyma jeden System przez cały czas działania programu,
+// dzięki czemu sysinfo ma poprzednią próbkę do liczenia zużycia CPU i przyrostów R/W
+pub struct Collector {
+    sys: System,
+    disks: Disks,
+    networks: Networks,
+    disk_stats: HashMap<String, DiskStats>, // poprzedni odczyt /proc/diskstats
+    block_devices: Vec<BlockDeviceInfo>, // obciążenie urządzeń między dwoma ostatnimi odczytami
+    cpu_times: HashMap<String, CpuTimes>, // poprzedni odczyt /proc/stat
+    cpu_breakdown: HashMap<String, CpuBreakdown>, // podział czasu procesorów między dwoma ostatnimi odczytami
+    cpu_model: CpuModel, // model i topologia się nie zmieniają, więc czytane są raz
+    cpu_topology: HashMap<String, CpuTopology>,
+    cpu_frequency: HashMap<String, CpuFrequency>, // ostatni odczyt cpufreq (brak wpisu - cpufreq niedostępne)
+    last_refresh: Instant, // czas poprzedniego odczytu sieci i urządzeń blokowych
+    elapsed: f64, // sekundy między dwoma ostatnimi odczytami
+    users: Users,
+    user_names: HashMap<Uid, String>, // podręczna mapa UID -> nazwa użytkownika
+    memory: HashMap<ProcessKey, ProcessMemory>, // ostatni odczyt pamięci procesów (bez wątków)
+    ttys: HashMap<ProcessKey, Option<String>>, // terminal sterujący - ustalany przy tworzeniu sesji, więc czytany raz na proces
+    ticks: u32,
+}
+
+impl Collector {
+    pub fn new() -> Self {
+        let mut sys = System::new();
+        // pierwsza próbka - punkt odniesienia dla kolejnych pomiarów
+        sys.refresh_processes_specifics(Self::process_refresh_kind());
+        sys.refresh_cpu_specifics(CpuRefreshKind::new().with_cpu_usage().with_frequency());
+        sys.refresh_memory();
+        // sysinfo potrzebuje minimalnego odstępu między próbkami CPU
+        std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+
+        let cpu_topology = sys
+            .cpus()
+            .iter()
+            .filter_map(|cpu| {
+                let index = cpu.name().strip_prefix("cpu")?.parse().ok()?;
+                Some((cpu.name().to_string(), read_cpu_topology(index)?))
+            })
+            .collect();
+
+        let users = Users::new_with_refreshed_list();
+        let user_names = Self::user_names(&users);
+
+        let mut collector = Self {
+            sys,
+            disks: Disks::new_with_refreshed_list(),
+            networks: Networks::new_with_refreshed_list(),
+            disk_stats: read_diskstats().into_iter().collect(),
+            block_devices: Vec::new(),
+            cpu_times: read_cpu_times().into_iter().collect(),
+            cpu_breakdown: HashMap::new(),
+            cpu_model: read_cpu_model(),
+            cpu_topology,
+            cpu_frequency: HashMap::new(),
+            last_refresh: Instant::now(),
+            elapsed: 0.0,
+            users,
+            user_names,
+            memory: HashMap::new(),
+            ttys: HashMap::new(),
+            ticks: 0,
+        };
+        collector.refresh_cpu_frequency(true);
+        collector
+    }
+
+    // odczyt cpufreq wszystkich procesorów, `all` - także zakresu i gubernatora, w przeciwnym razie
+    // tylko bieżącej częstotliwości
+    fn refresh_cpu_frequency(&mut self, all: bool) {
+        for cpu in self.sys.cpus() {
+            let Some(index) = cpu.name().strip_prefix("cpu").and_then(|index| index.parse().ok()) else {
+                continue;
+            };
+            if all {
+                self.cpu_frequency.insert(cpu.name().to_string(), read_cpu_frequency(index));
+            } else if let Some(frequency) = self.cpu_frequency.get_mut(cpu.name()) {
+                frequency.current = read_cpu_current_frequency(index);
+            }
+        }
+    }
+
+    // budowanie mapy UID -> nazwa, żeby nie przeszukiwać listy dla każdego procesu
+    fn user_names(users: &Users) -> HashMap<Uid, String> {
+        users.iter().map(|user| (user.id().clone(), user.name().to_string())).collect()
+    }
+
+    // odczyt pamięci procesów (bez wątków - dzielą przestrzeń adresową z procesem),
+    // `all` - wszystkich, w przeciwnym razie tylko tych, których jeszcze nie znamy
+    fn refresh_process_memory(&mut self, all: bool) {
+        if all {
+            self.memory.clear();
+        }
+        for process in self.sys.processes().values() {
+            if process.thread_kind() == Some(ThreadKind::Userland) {
+                continue;
+            }
+            let key = ProcessKey { pid: process.pid(), start_time: process.start_time() };
+            self.memory.entry(key).or_insert_with(|| ProcessMemory {
+                status: read_status_memory(process.pid()),
+                pss: read_pss(process.pid()),
+            });
+        }
+    }
+
+    // tylko to, co faktycznie pokazujemy w tabeli procesów
+    fn process_refresh_kind() -> ProcessRefreshKind {
+        ProcessRefreshKind::new()
+            .with_cpu()
+            .with_memory()
+            .with_disk_usage()
+            .with_exe(UpdateKind::OnlyIfNotSet)
+            .with_user(UpdateKind::OnlyIfNotSet)
+            .with_cmd(UpdateKind::OnlyIfNotSet)
+    }
+
+}
+
+// źródło danych "na żywo" - sysinfo
+impl DataSource for Collector {
+    // odświeżanie danych - procesy, CPU, RAM i sieć co każde wywołanie, dyski rzadziej
+    fn refresh(&mut self) {
+        self.ticks = self.ticks.wrapping_add(1);
+
+        self.sys.refresh_processes_specifics(Self::process_refresh_kind());
+        self.sys.refresh_cpu_specifics(CpuRefreshKind::new().with_cpu_usage().with_frequency());
+        self.sys.refresh_memory();
+
+        // terminal czytam tylko dla nowych procesów, zakończone usuwam
+        let processes = self.sys.processes();
+        self.ttys.retain(|key, _| processes.get(&key.pid).is_some_and(|process| process.start_time() == key.start_time));
+        for process in processes.values() {
+            let key = ProcessKey { pid: process.pid(), start_time: process.start_time() };
+            self.ttys.entry(key).or_insert_with(|| read_tty(process.pid()));
+        }
+
+        if self.ticks.is_multiple_of(DISK_LIST_REFRESH_EVERY) {
+            self.disks.refresh_list();
+        } else if self.ticks.is_multiple_of(DISK_REFRESH_EVERY) {
+            self.disks.refresh();
+        }
+
+        // przyrosty sieci i dysków liczone są od poprzedniego odczytu, więc zapisuję odstęp
+        if self.ticks.is_multiple_of(NETWORK_LIST_REFRESH_EVERY) {
+            self.networks.refresh_list();
+        } else {
+            self.networks.refresh();
+        }
+        let now = Instant::now();
+        self.elapsed = now.duration_since(self.last_refresh).as_secs_f64();
+        self.last_refresh = now;
+
+        let disk_stats: HashMap<String, DiskStats> = read_diskstats().into_iter().collect();
+        let mut block_devices: Vec<BlockDeviceInfo> = disk_stats
+            .iter()
+            .filter_map(|(name, current)| {
+                let previous = self.disk_stats.get(name)?;
+                Some(BlockDeviceInfo::from_delta(name, previous, current, self.elapsed))
+            })
+            .collect();
+        block_devices.sort_by(|a, b| a.name.cmp(&b.name));
+        self.block_devices = block_devices;
+        self.disk_stats = disk_stats;
+
+        let cpu_times: HashMap<String, CpuTimes> = read_cpu_times().into_iter().collect();
+        self.cpu_breakdown = cpu_times
+            .iter()
+            .filter_map(|(name, current)| {
+                let previous = self.cpu_times.get(name)?;
+                Some((name.clone(), CpuBreakdown::from_delta(previous, current)))
+            })
+            .collect();
+        self.cpu_times = cpu_times;
+
+        if self.ticks.is_multiple_of(USERS_REFRESH_EVERY) {
+            self.users.refresh_list();
+            self.user_names = Self::user_names(&self.users);
+        }
+
+        self.refresh_process_memory(self.ticks.is_multiple_of(MEMORY_REFRESH_EVERY));
+        self.refresh_cpu_frequency(self.ticks.is_multiple_of(CPU_FREQUENCY_REFRESH_EVERY));
+    }
+
+    fn processes(&mut self) -> Vec<ProcessInfo> {
+        self.sys
+            .processes()
+            .values()
+            .map(|process| {
+                let mut proc = ProcessInfo::get_data_from_process(process, &self.user_names);
+                if let Some(memory) = self.memory.get(&proc.key()) {
+                    proc.shared_mem = memory.status.map(|status| status.shared);
+                    proc.anon_mem = memory.status.map(|status| status.anon);
+                    proc.swap_mem = memory.status.map(|status| status.swap);
+                    proc.pss = memory.pss;
+                }
+                proc.tty = self.ttys.get(&proc.key()).cloned().flatten();
+                proc
+            })
+            .collect()
+    }
+
+    fn general_info(&mut self) -> GeneralInfo {
+        let mut info = GeneralInfo::get_general_data(&self.sys, &self.disks, &self.networks, self.elapsed);
+        info.block_device_tab = self.block_devices.clone();
+        // sysinfo nazywa procesory tak samo jak /proc/stat ("cpu0", "cpu1", ...)
+        for cpu in &mut info.cpu_usage_tab {
+            cpu.breakdown = self.cpu_breakdown.get(&cpu.name).copied();
+            cpu.topology = self.cpu_topology.get(&cpu.name).copied();
+            // bez cpufreq zostaje częstotliwość z sysinfo
+            if let Some(frequency) = self.cpu_frequency.get(&cpu.name) {
+                let current = frequency.current.or(cpu.frequency.current);
+                cpu.frequency = CpuFrequency { current, ..frequency.clone() };
+            }
+        }
+        info.cpu_model = self.cpu_model.clone();
+        info
+    }
+}
@@ -0,0 +1,553 @@
+use std::fmt;
+
+use regex::{Regex, RegexBuilder};
+
+use super::{ProcessInfo, ProcessName};
+use crate::utils::{CpuMode, Units};
+
+// język filtrów, np. `cpu > 5 and mem > 200MB and user == "ci"`
+//
+// wyrażenie := lub
+// lub        := i ("or" | "||") i ...
+// i          := nie ("and" | "&&") nie ...
+// nie        := ("not" | "!") nie | "(" wyrażenie ")" | porównanie
+// porównanie := pole (== | = | != | > | >= | < | <= | ~ | !~) wartość
+// wartość    := liczba[jednostka] | "tekst" | słowo
+
+// błąd parsowania z pozycją (indeks znaku) w wyrażeniu
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterError {
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for FilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (column {})", self.message, self.position + 1)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Match,
+    NotMatch,
+}
+
+// liczba z jednostką: `value` razy podstawa jednostek do potęgi `power`
+// K/M/G/T liczone są według aktywnego ustawienia Units, KiB/MiB/GiB/TiB zawsze w potęgach 1024
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Quantity {
+    value: f64,
+    power: i32,
+    binary: bool,
+}
+
+impl Quantity {
+    fn resolve(&self, units: Units) -> f64 {
+        let base = if self.binary { Units::Binary.base() } else { units.base() };
+        self.value * base.powi(self.power)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Value {
+    Number(Quantity),
+    Text(String),
+    Pattern(Regex),
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Compare { field: ProcessName, op: CompareOp, value: Value },
+}
+
+// sparsowany filtr razem z tekstem, z którego powstał
+#[derive(Debug, Clone)]
+pub struct Filter {
+    pub source: String,
+    expr: Expr,
+}
+
+impl Filter {
+    pub fn parse(source: &str) -> Result<Filter, FilterError> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser { tokens, pos: 0, end: source.chars().count() };
+        let expr = parser.parse_or()?;
+        if let Some(token) = parser.peek() {
+            return Err(FilterError {
+                position: token.position,
+                message: format!("unexpected {}", token.kind.describe()),
+            });
+        }
+        Ok(Filter { source: source.trim().to_string(), expr })
+    }
+
+    // `cpu_mode` i `cpus` - jak liczony jest procent CPU porównywany w polu `cpu`,
+    // `units` - czy rozmiary K, M, G, T w wyrażeniu to potęgi 1024 czy 1000
+    pub fn matches(&self, proc: &ProcessInfo, cpu_mode: CpuMode, units: Units, cpus: usize) -> bool {
+        eval(&self.expr, proc, cpu_mode, units, cpus)
+    }
+}
+
+// wartość pola procesu użyta w porównaniu
+enum FieldValue {
+    Number(f64),
+    Text(String),
+}
+
+fn field_value(proc: &ProcessInfo, field: ProcessName, cpu_mode: CpuMode, cpus: usize) -> Option<FieldValue> {
+    let number = |v: Option<f64>| v.map(FieldValue::Number);
+    match field {
+        ProcessName::PID => Some(FieldValue::Number(proc.pid.as_u32() as f64)),
+        ProcessName::NAME => Some(FieldValue::Text(proc.name.clone())),
+        ProcessName::CPU => number(proc.cpu_percent(cpu_mode, cpus)),
+        ProcessName::MEM => number(proc.rss.map(|v| v as f64)),
+        ProcessName::READ => number(proc.read_bytes.map(|v| v as f64)),
+        ProcessName::WRITE => number(proc.write_bytes.map(|v| v as f64)),
+        ProcessName::TOTAL_READ => number(proc.total_read.map(|v| v as f64)),
+        ProcessName::TOTAL_WRITTEN => number(proc.total_written.map(|v| v as f64)),
+        ProcessName::USER => proc.user.clone().map(FieldValue::Text),
+        ProcessName::PPID => number(proc.parent.map(|pid| pid.as_u32() as f64)),
+        ProcessName::STATE => Some(FieldValue::Text(proc.status.clone())),
+        ProcessName::THREADS => number(proc.threads.map(|v| v as f64)),
+        ProcessName::VIRT => number(proc.virtual_mem.map(|v| v as f64)),
+        ProcessName::SHARED => number(proc.shared_mem.map(|v| v as f64)),
+        ProcessName::ANON => number(proc.anon_mem.map(|v| v as f64)),
+        ProcessName::SWAP => number(proc.swap_mem.map(|v| v as f64)),
+        ProcessName::PSS => number(proc.pss.map(|v| v as f64)),
+        ProcessName::START => Some(FieldValue::Number(proc.start_time as f64)),
+        ProcessName::TIME => Some(FieldValue::Number(proc.run_time as f64)),
+        ProcessName::TTY => proc.tty.clone().map(FieldValue::Text),
+        ProcessName::COMMAND => Some(FieldValue::Text(proc.command_line())),
+    }
+}
+
+fn eval(expr: &Expr, proc: &ProcessInfo, cpu_mode: CpuMode, units: Units, cpus: usize) -> bool {
+    match expr {
+        Expr::And(a, b) => eval(a, proc, cpu_mode, units, cpus) && eval(b, proc, cpu_mode, units, cpus),
+        Expr::Or(a, b) => eval(a, proc, cpu_mode, units, cpus) || eval(b, proc, cpu_mode, units, cpus),
+        Expr::Not(a) => !eval(a, proc, cpu_mode, units, cpus),
+        Expr::Compare { field, op, value } => {
+            // brak danych (np. nieznany użytkownik) nigdy nie pasuje
+            let Some(actual) = field_value(proc, *field, cpu_mode, cpus) else {
+                return false;
+            };
+            match (actual, value) {
+                (FieldValue::Number(a), Value::Number(b)) => compare(a.partial_cmp(&b.resolve(units)), *op),
+                (FieldValue::Text(a), Value::Text(b)) => compare(Some(a.as_str().cmp(b.as_str())), *op),
+                (FieldValue::Text(a), Value::Pattern(re)) => re.is_match(&a) == (*op == CompareOp::Match),
+                // pozostałe kombinacje odrzuca parser
+                _ => false,
+            }
+        }
+    }
+}
+
+fn compare(ordering: Option<std::cmp::Ordering>, op: CompareOp) -> bool {
+    use std::cmp::Ordering::*;
+    let Some(ordering) = ordering else {
+        return false;
+    };
+    match op {
+        CompareOp::Eq => ordering == Equal,
+        CompareOp::Ne => ordering != Equal,
+        CompareOp::Gt => ordering == Greater,
+        CompareOp::Ge => ordering != Less,
+        CompareOp::Lt => ordering == Less,
+        CompareOp::Le => ordering != Greater,
+        CompareOp::Match | CompareOp::NotMatch => false,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Word(String),
+    Number { value: Quantity, raw: String },
+    Text(String),
+    Op(CompareOp),
+    And,
+    Or,
+    Not,
+    LParen,
+    RParen,
+}
+
+impl TokenKind {
+    fn describe(&self) -> String {
+        match self {
+            TokenKind::Word(w) => format!("`{w}`"),
+            TokenKind::Number { raw, .. } => format!("`{raw}`"),
+            TokenKind::Text(t) => format!("\"{t}\""),
+            TokenKind::Op(_) => "operator".to_string(),
+            TokenKind::And => "`and`".to_string(),
+            TokenKind::Or => "`or`".to_string(),
+            TokenKind::Not => "`not`".to_string(),
+            TokenKind::LParen => "`(`".to_string(),
+            TokenKind::RParen => "`)`".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    position: usize,
+}
+
+// potęga dla jednostki przy liczbie i czy jest zawsze binarna (KiB, MiB, ...)
+fn unit_power(unit: &str) -> Option<(i32, bool)> {
+    let unit = unit.to_lowercase();
+    let binary = unit.len() == 3 && unit.ends_with("ib");
+    let power = match unit.as_str() {
+        "" | "%" | "b" => 0,
+        "k" | "kb" | "kib" => 1,
+        "m" | "mb" | "mib" => 2,
+        "g" | "gb" | "gib" => 3,
+        "t" | "tb" | "tib" => 4,
+        _ => return None,
+    };
+    Some((power, binary))
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '.' | '/' | ':' | '-')
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, FilterError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        let error = |message: String| FilterError { position: start, message };
+        let two = |next: char| chars.get(i + 1) == Some(&next);
+
+        let (kind, len) = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '(' => (TokenKind::LParen, 1),
+            ')' => (TokenKind::RParen, 1),
+            '=' if two('=') => (TokenKind::Op(CompareOp::Eq), 2),
+            '=' => (TokenKind::Op(CompareOp::Eq), 1),
+            '!' if two('=') => (TokenKind::Op(CompareOp::Ne), 2),
+            '!' if two('~') => (TokenKind::Op(CompareOp::NotMatch), 2),
+            '!' => (TokenKind::Not, 1),
+            '>' if two('=') => (TokenKind::Op(CompareOp::Ge), 2),
+            '>' => (TokenKind::Op(CompareOp::Gt), 1),
+            '<' if two('=') => (TokenKind::Op(CompareOp::Le), 2),
+            '<' => (TokenKind::Op(CompareOp::Lt), 1),
+            '~' => (TokenKind::Op(CompareOp::Match), 1),
+            '&' if two('&') => (TokenKind::And, 2),
+            '|' if two('|') => (TokenKind::Or, 2),
+            '"' | '\'' => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|&ch| ch == c)
+                    .ok_or_else(|| error("unterminated string".to_string()))?;
+                let text: String = chars[i + 1..i + 1 + end].iter().collect();
+                (TokenKind::Text(text), end + 2)
+            }
+            // liczba (także ujemna) z opcjonalną jednostką
+            c if c.is_ascii_digit() || (c == '-' && chars.get(i + 1).is_some_and(char::is_ascii_digit)) => {
+                let sign = usize::from(c == '-');
+                let digits = sign + chars[i + sign..].iter().take_while(|ch| ch.is_ascii_digit() || **ch == '.').count();
+                let unit_len = chars[i + digits..]
+                    .iter()
+                    .take_while(|ch| ch.is_alphabetic() || **ch == '%')
+                    .count();
+                let word_len = chars[i..].iter().take_while(|ch| is_word_char(**ch)).count();
+                let number: String = chars[i..i + digits].iter().collect();
+                let unit: String = chars[i + digits..i + digits + unit_len].iter().collect();
+                match (number.parse::<f64>(), unit_power(&unit)) {
+                    (Ok(value), Some((power, binary))) if digits + unit_len >= word_len => {
+                        let raw = format!("{number}{unit}");
+                        (TokenKind::Number { value: Quantity { value, power, binary }, raw }, digits + unit_len)
+                    }
+                    // to nie liczba, tylko słowo zaczynające się od cyfry (np. `7zip`, `1.2.3`)
+                    _ => (TokenKind::Word(chars[i..i + word_len].iter().collect()), word_len),
+                }
+            }
+            c if is_word_char(c) => {
+                let len = chars[i..].iter().take_while(|ch| is_word_char(**ch)).count();
+                let word: String = chars[i..i + len].iter().collect();
+                let kind = match word.to_lowercase().as_str() {
+                    "and" => TokenKind::And,
+                    "or" => TokenKind::Or,
+                    "not" => TokenKind::Not,
+                    _ => TokenKind::Word(word),
+                };
+                (kind, len)
+            }
+            c => return Err(error(format!("unexpected character `{c}`"))),
+        };
+        tokens.push(Token { kind, position: start });
+        i += len;
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    end: usize, // długość wyrażenia - pozycja błędów typu "nieoczekiwany koniec"
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Result<Token, FilterError> {
+        let token = self.tokens.get(self.pos).cloned().ok_or_else(|| FilterError {
+            position: self.end,
+            message: "unexpected end of expression".to_string(),
+        })?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn eat(&mut self, kind: &TokenKind) -> bool {
+        if self.peek().map(|t| &t.kind) == Some(kind) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, FilterError> {
+        let mut left = self.parse_and()?;
+        while self.eat(&TokenKind::Or) {
+            left = Expr::Or(Box::new(left), Box::new(self.parse_and()?));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, FilterError> {
+        let mut left = self.parse_not()?;
+        while self.eat(&TokenKind::And) {
+            left = Expr::And(Box::new(left), Box::new(self.parse_not()?));
+        }
+        Ok(left)
+    }
+
+    fn parse_not(&mut self) -> Result<Expr, FilterError> {
+        if self.eat(&TokenKind::Not) {
+            return Ok(Expr::Not(Box::new(self.parse_not()?)));
+        }
+        if self.eat(&TokenKind::LParen) {
+            let expr = self.parse_or()?;
+            let token = self.next()?;
+            if token.kind != TokenKind::RParen {
+                return Err(FilterError {
+                    position: token.position,
+                    message: format!("expected `)`, found {}", token.kind.describe()),
+                });
+            }
+            return Ok(expr);
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr, FilterError> {
+        let token = self.next()?;
+        let TokenKind::Word(name) = &token.kind else {
+            return Err(FilterError {
+                position: token.position,
+                message: format!("expected a field name, found {}", token.kind.describe()),
+            });
+        };
+        let field = ProcessName::from_field_name(name).ok_or_else(|| FilterError {
+            position: token.position,
+            message: format!(
+                "unknown field `{name}` (expected one of: {})",
+                ProcessName::ALL.iter().map(|f| f.field_name()).collect::<Vec<_>>().join(", ")
+            ),
+        })?;
+
+        let token = self.next()?;
+        let TokenKind::Op(op) = token.kind else {
+            return Err(FilterError {
+                position: token.position,
+                message: format!("expected a comparison operator after `{name}`, found {}", token.kind.describe()),
+            });
+        };
+
+        let token = self.next()?;
+        let error = |message: String| FilterError { position: token.position, message };
+        let value = match (&token.kind, !field.is_text(), op) {
+            (TokenKind::Number { .. } | TokenKind::Text(_) | TokenKind::Word(_), true, CompareOp::Match | CompareOp::NotMatch) => {
+                return Err(error(format!("`~` only works on text fields, `{name}` is numeric")));
+            }
+            (TokenKind::Number { value, .. }, true, _) => Value::Number(*value),
+            (TokenKind::Word(word), true, _) if word.starts_with(|c: char| c.is_ascii_digit() || c == '-') => {
+                return Err(error(format!("invalid number `{word}` (sizes take K, M, G or T)")));
+            }
+            (TokenKind::Text(_) | TokenKind::Word(_), true, _) => {
+                return Err(error(format!("field `{name}` expects a number, found {}", token.kind.describe())));
+            }
+            (TokenKind::Number { raw: text, .. } | TokenKind::Text(text) | TokenKind::Word(text), false, CompareOp::Match | CompareOp::NotMatch) => {
+                let pattern = RegexBuilder::new(text)
+                    .case_insensitive(true)
+                    .build()
+                    .map_err(|err| error(format!("invalid regex: {}", err.to_string().lines().last().unwrap_or("").trim())))?;
+                Value::Pattern(pattern)
+            }
+            (TokenKind::Number { raw: text, .. } | TokenKind::Text(text) | TokenKind::Word(text), false, _) => {
+                Value::Text(text.clone())
+            }
+            (kind, _, _) => return Err(error(format!("expected a value, found {}", kind.describe()))),
+        };
+        Ok(Expr::Compare { field, op, value })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(pid: u32, name: &str, cpu: f32, rss: u64) -> ProcessInfo {
+        let mut proc = ProcessInfo::with_name(pid, name);
+        proc.cpu = Some(cpu);
+        proc.rss = Some(rss);
+        proc.user = Some("ci".to_string());
+        proc
+    }
+
+    fn matches(source: &str, proc: &ProcessInfo) -> bool {
+        Filter::parse(source).unwrap().matches(proc, CpuMode::Irix, Units::Binary, 1)
+    }
+
+    fn error(source: &str) -> FilterError {
+        Filter::parse(source).unwrap_err()
+    }
+
+    #[test]
+    fn not_binds_tighter_than_and_tighter_than_or() {
+        let bash = process(1, "bash", 10.0, 0);
+        let sshd = process(2, "sshd", 0.0, 0);
+        // ((not name == "bash") and cpu > 5) or pid == 1
+        let source = r#"not name == "bash" and cpu > 5 or pid == 1"#;
+        assert!(matches(source, &bash));
+        assert!(!matches(source, &sshd));
+        assert!(matches("name == bash or name == sshd and cpu > 5", &bash));
+        assert!(!matches("! name == bash", &bash));
+        assert!(matches("not not name == bash", &bash));
+    }
+
+    #[test]
+    fn parentheses_override_precedence() {
+        let bash = process(1, "bash", 10.0, 0);
+        assert!(!matches("name == sshd and (pid == 1 or cpu > 5)", &bash));
+        assert!(matches("(name == sshd or pid == 1) && cpu > 5", &bash));
+        assert!(matches("((pid == 1))", &bash));
+    }
+
+    #[test]
+    fn sizes_take_binary_units_by_default() {
+        let proc = process(1, "db", 0.0, 300 * 1024 * 1024);
+        assert!(matches("mem > 200MB", &proc));
+        assert!(matches("mem < 1g", &proc));
+        assert!(matches("mem == 307200K", &proc));
+        assert!(matches("rss >= 300MiB", &proc));
+        assert!(!matches("mem > 0.5GB", &proc));
+        assert!(matches("cpu >= 0%", &proc));
+    }
+
+    #[test]
+    fn sizes_follow_the_units_setting() {
+        let proc = process(1, "db", 0.0, 1_500_000_000);
+        let filter = Filter::parse("mem > 1.4G").unwrap();
+        assert!(filter.matches(&proc, CpuMode::Irix, Units::Decimal, 1));
+        assert!(!filter.matches(&proc, CpuMode::Irix, Units::Binary, 1));
+        // KiB, MiB, GiB, TiB zawsze są binarne
+        let filter = Filter::parse("mem < 1.4GiB").unwrap();
+        assert!(filter.matches(&proc, CpuMode::Irix, Units::Decimal, 1));
+    }
+
+    #[test]
+    fn negative_numbers_are_numbers() {
+        let proc = process(1, "idle", 0.0, 0);
+        assert!(matches("cpu > -1", &proc));
+        assert!(!matches("cpu < -0.5", &proc));
+    }
+
+    #[test]
+    fn words_starting_with_a_digit_are_text() {
+        let proc = process(1, "7zip", 0.0, 0);
+        assert!(matches("name == 7zip", &proc));
+        assert!(matches("name ~ 7z", &proc));
+        assert!(!matches("name == 1.2.3", &proc));
+        assert!(matches("name != 5", &proc));
+    }
+
+    #[test]
+    fn strings_and_words_compare_as_text() {
+        let mut proc = process(1, "my server", 0.0, 0);
+        proc.cmd = vec!["/usr/bin/server".to_string(), "--port=80".to_string()];
+        assert!(matches(r#"name == "my server""#, &proc));
+        assert!(matches("name == 'my server'", &proc));
+        assert!(matches("user == ci and user != root", &proc));
+        assert!(matches("command == \"/usr/bin/server --port=80\"", &proc));
+        // brak danych nigdy nie pasuje, także przy !=
+        assert!(!matches("tty != pts/1", &proc));
+    }
+
+    #[test]
+    fn regex_operators_are_case_insensitive() {
+        let proc = process(1, "PostgreSQL", 0.0, 0);
+        assert!(matches(r#"name ~ "^postgres""#, &proc));
+        assert!(matches("name ~ SQL", &proc));
+        assert!(matches(r#"name ~ "sql$""#, &proc));
+        assert!(!matches("name !~ gres", &proc));
+        assert!(matches("name !~ mysql", &proc));
+    }
+
+    #[test]
+    fn cpu_follows_the_cpu_mode() {
+        let proc = process(1, "busy", 200.0, 0);
+        let filter = Filter::parse("cpu > 100").unwrap();
+        assert!(filter.matches(&proc, CpuMode::Irix, Units::Binary, 4));
+        assert!(!filter.matches(&proc, CpuMode::Solaris, Units::Binary, 4));
+    }
+
+    #[test]
+    fn errors_point_at_the_offending_token() {
+        assert_eq!(error("cpu >").position, 5);
+        assert_eq!(error("cpu >").message, "unexpected end of expression");
+
+        let unknown = error("pid > 1 and foo > 1");
+        assert_eq!(unknown.position, 12);
+        assert!(unknown.message.starts_with("unknown field `foo`"));
+
+        assert_eq!(error("cpu > 5 )"), FilterError { position: 8, message: "unexpected `)`".to_string() });
+        assert_eq!(error("(cpu > 5 pid").position, 9);
+        assert_eq!(error(r#"name == "bash"#).message, "unterminated string");
+        assert_eq!(error(r#"name == "bash"#).position, 8);
+        assert_eq!(error("cpu $ 5").message, "unexpected character `$`");
+        assert_eq!(error("cpu 5").message, "expected a comparison operator after `cpu`, found `5`");
+        assert_eq!(error("mem > fast").message, "field `mem` expects a number, found `fast`");
+        assert_eq!(error("mem > 200XB").message, "invalid number `200XB` (sizes take K, M, G or T)");
+        assert_eq!(error("cpu ~ 5").message, "`~` only works on text fields, `cpu` is numeric");
+        assert!(error("name ~ \"(\"").message.starts_with("invalid regex"));
+        assert_eq!(error("name ~ \"(\"").position, 7);
+    }
+
+    #[test]
+    fn display_uses_one_based_columns() {
+        assert_eq!(error("cpu >").to_string(), "unexpected end of expression (column 6)");
+    }
+}
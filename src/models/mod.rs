@@ -1,7 +1,17 @@
+mod collector;
+mod filter;
 mod process;
+mod procfs;
+mod signal;
+mod source;
 mod system;
 mod types;
 
+pub use collector::*;
+pub use filter::*;
 pub use process::*;
+pub use procfs::*;
+pub use signal::*;
+pub use source::*;
 pub use system::*;
 pub use types::*; 
\ No newline at end of file
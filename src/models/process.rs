@@ -1,51 +1,305 @@
-use std::collections::VecDeque;
-use sysinfo::{Pid, Process, System};
+use std::cmp::Ordering;
+use std::collections::{HashMap, VecDeque};
+use std::time::Instant;
+use serde::{Deserialize, Serialize};
+use sysinfo::{Pid, Process, ThreadKind, Uid};
+
+use super::{ProcessName, COMMAND_WIDTH};
+use crate::utils::{CpuMode, Units, format_duration, format_option, format_option_units, format_timestamp};
+
+// identyfikator procesu odporny na ponowne użycie PID (PID + czas startu)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ProcessKey {
+    pub pid: Pid,
+    pub start_time: u64,
+}
 
 // struktura przechowująca informacje o danym procesie
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProcessInfo {
+    #[serde(with = "as_text")]
     pub pid: Pid,
+    #[serde(with = "as_text::option")]
+    pub parent: Option<Pid>, // PID rodzica (do widoku drzewa)
+    pub is_thread: bool, // wątek użytkownika (zadanie z /proc/<pid>/task), a nie osobny proces
     pub name: String,
-    pub cpu: Option<f32>,
-    pub mem_mb: Option<f64>,
+    pub cpu: Option<f32>, // 100% to jeden procesor logiczny - do wyświetlania przez `cpu_percent`
+    pub rss: Option<u64>, // pamięć rezydentna w bajtach
     pub read_bytes: Option<u64>,
     pub write_bytes: Option<u64>,
     pub total_read: Option<u64>,
     pub total_written: Option<u64>,
-    pub user: Option<String>,
+    pub user: Option<String>, // nazwa efektywnego właściciela procesu
+    pub real_user: Option<String>, // nazwa rzeczywistego właściciela procesu
+    #[serde(with = "as_text::uid")]
+    pub uid: Option<Uid>, // rzeczywisty UID
+    #[serde(with = "as_text::uid")]
+    pub euid: Option<Uid>, // efektywny UID
+    pub cmd: Vec<String>, // pełna linia poleceń
+    pub exe: Option<String>, // ścieżka do pliku wykonywalnego
+    pub status: String, // stan procesu (Run, Sleep, ...)
+    pub start_time: u64, // czas startu (sekundy od epoki unix)
+    pub run_time: u64, // czas działania w sekundach
+    #[serde(with = "as_text::option")]
+    pub session: Option<Pid>, // identyfikator sesji
+    pub tty: Option<String>, // terminal sterujący (uzupełnia kolektor)
+    pub threads: Option<usize>, // liczba wątków (brak dla samych wątków)
+    pub virtual_mem: Option<u64>, // pamięć wirtualna w bajtach
+    pub shared_mem: Option<u64>, // część RSS współdzielona (pliki, shm)
+    pub anon_mem: Option<u64>, // część RSS prywatna (anonimowa)
+    pub swap_mem: Option<u64>, // w swapie
+    pub pss: Option<u64>, // RSS z pamięcią współdzieloną podzieloną między procesy
 }
 
 impl ProcessInfo {
     // pobieranie danych o DANYM procesie
-    pub fn get_data_from_process(process: &Process, sys: &System) -> Self {
+    pub fn get_data_from_process(process: &Process, user_names: &HashMap<Uid, String>) -> Self {
         let disk = process.disk_usage();
-        let mem_mb = Some(process.memory() as f64);
-        let usage = Some(process.cpu_usage());
-        let core_count = sys.physical_core_count().unwrap_or(1) as f32;
-        let percent_of_total = usage.map(|u| (u / (core_count)) );
+        let uid = process.user_id().cloned();
+        let euid = process.effective_user_id().cloned().or_else(|| uid.clone());
+        // nazwa użytkownika z tablicy, a jeśli jej brak - sam numer UID
+        let resolve = |id: &Uid| user_names.get(id).cloned().unwrap_or_else(|| (**id).to_string());
+        let user = euid.as_ref().map(resolve);
+        let real_user = uid.as_ref().map(resolve);
 
         Self {
             pid: process.pid(),
+            parent: process.parent(),
+            is_thread: process.thread_kind() == Some(ThreadKind::Userland),
             name: process.name().to_string(),
-            cpu: percent_of_total,
-            mem_mb,
+            cpu: Some(process.cpu_usage()),
+            rss: Some(process.memory()),
             read_bytes: Some(disk.read_bytes),
             write_bytes: Some(disk.written_bytes),
             total_read: Some(disk.total_read_bytes),
             total_written: Some(disk.total_written_bytes),
+            user,
+            real_user,
+            uid,
+            euid,
+            cmd: process.cmd().to_vec(),
+            exe: process.exe().map(|p| p.display().to_string()),
+            status: process.status().to_string(),
+            start_time: process.start_time(),
+            run_time: process.run_time(),
+            session: process.session_id(),
+            tty: None,
+            // lista zadań nie zawiera głównego wątku procesu
+            threads: process.tasks().map(|tasks| tasks.len() + 1),
+            virtual_mem: Some(process.virtual_memory()),
+            // rozbicie pamięci uzupełnia kolektor
+            shared_mem: None,
+            anon_mem: None,
+            swap_mem: None,
+            pss: None,
+        }
+    }
+
+    // tekst komórki tabeli dla danej kolumny (procent CPU według `cpu_mode` przy `cpus` procesorach logicznych)
+    pub fn column_text(&self, column: ProcessName, units: Units, cpu_mode: CpuMode, cpus: usize) -> String {
+        let units = |v: Option<u64>| format_option_units(v.map(|v| v as f64), units);
+        match column {
+            ProcessName::PID => self.pid.to_string(),
+            ProcessName::NAME => self.name.clone(),
+            ProcessName::CPU => format_option(self.cpu_percent(cpu_mode, cpus).map(|v| format!("{:.1}", v))),
+            ProcessName::MEM => units(self.rss),
+            ProcessName::READ => units(self.read_bytes),
+            ProcessName::WRITE => units(self.write_bytes),
+            ProcessName::TOTAL_READ => units(self.total_read),
+            ProcessName::TOTAL_WRITTEN => units(self.total_written),
+            ProcessName::USER => format_option(self.user_label()),
+            ProcessName::PPID => format_option(self.parent),
+            ProcessName::STATE => self.status.clone(),
+            ProcessName::THREADS => format_option(self.threads),
+            ProcessName::VIRT => units(self.virtual_mem),
+            ProcessName::SHARED => units(self.shared_mem),
+            ProcessName::ANON => units(self.anon_mem),
+            ProcessName::SWAP => units(self.swap_mem),
+            ProcessName::PSS => units(self.pss),
+            ProcessName::START => format_timestamp(self.start_time),
+            ProcessName::TIME => format_duration(self.run_time),
+            ProcessName::TTY => format_option(self.tty.clone()),
+            ProcessName::COMMAND => self.command_line().chars().take(COMMAND_WIDTH).collect(),
+        }
+    }
+
+    // porównanie dwóch procesów według danej kolumny (do sortowania)
+    pub fn compare(&self, other: &Self, column: ProcessName) -> Ordering {
+        let float = |a: Option<f64>, b: Option<f64>| a.partial_cmp(&b).unwrap_or(Ordering::Equal);
+        match column {
+            ProcessName::PID => self.pid.cmp(&other.pid),
+            ProcessName::NAME => self.name.cmp(&other.name),
+            ProcessName::CPU => float(self.cpu.map(f64::from), other.cpu.map(f64::from)),
+            ProcessName::MEM => self.rss.cmp(&other.rss),
+            ProcessName::READ => self.read_bytes.cmp(&other.read_bytes),
+            ProcessName::WRITE => self.write_bytes.cmp(&other.write_bytes),
+            ProcessName::TOTAL_READ => self.total_read.cmp(&other.total_read),
+            ProcessName::TOTAL_WRITTEN => self.total_written.cmp(&other.total_written),
+            ProcessName::USER => self.user.cmp(&other.user),
+            ProcessName::PPID => self.parent.cmp(&other.parent),
+            ProcessName::STATE => self.status.cmp(&other.status),
+            ProcessName::THREADS => self.threads.cmp(&other.threads),
+            ProcessName::VIRT => self.virtual_mem.cmp(&other.virtual_mem),
+            ProcessName::SHARED => self.shared_mem.cmp(&other.shared_mem),
+            ProcessName::ANON => self.anon_mem.cmp(&other.anon_mem),
+            ProcessName::SWAP => self.swap_mem.cmp(&other.swap_mem),
+            ProcessName::PSS => self.pss.cmp(&other.pss),
+            ProcessName::START => self.start_time.cmp(&other.start_time),
+            ProcessName::TIME => self.run_time.cmp(&other.run_time),
+            ProcessName::TTY => self.tty.cmp(&other.tty),
+            ProcessName::COMMAND => self.cmd.cmp(&other.cmd),
+        }
+    }
+
+    // zużycie CPU w wybranym trybie (Irix albo Solaris) przy `cpus` procesorach logicznych
+    pub fn cpu_percent(&self, mode: CpuMode, cpus: usize) -> Option<f64> {
+        self.cpu.map(|usage| mode.normalize(usage as f64, cpus))
+    }
+
+    // klucz procesu (do historii wykresów)
+    pub fn key(&self) -> ProcessKey {
+        ProcessKey { pid: self.pid, start_time: self.start_time }
+    }
+
+    // linia poleceń jako jeden napis (dla wątków jądra pusta)
+    pub fn command_line(&self) -> String {
+        self.cmd.join(" ")
+    }
+
+    // opis właściciela do tabeli - przy innym rzeczywistym UID (np. sudo, setuid) dopisuję go w nawiasie
+    pub fn user_label(&self) -> Option<String> {
+        match (&self.user, &self.real_user) {
+            (Some(user), Some(real_user)) if self.uid != self.euid => Some(format!("{user} ({real_user})")),
+            (user, _) => user.clone(),
+        }
+    }
+}
+
+// sysinfo nie potrafi zapisać ani odczytać Pid i Uid przez serde - w nagraniach (--record) zapisuję je jako tekst
+mod as_text {
+    use std::fmt::Display;
+    use std::str::FromStr;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<T: Display, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?.parse().map_err(D::Error::custom)
+    }
+
+    pub mod option {
+        use std::fmt::Display;
+        use std::str::FromStr;
+        use serde::de::Error;
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+        pub fn serialize<T: Display, S: Serializer>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error> {
+            value.as_ref().map(ToString::to_string).serialize(serializer)
+        }
+
+        pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+        where
+            T: FromStr,
+            T::Err: Display,
+            D: Deserializer<'de>,
+        {
+            Option::<String>::deserialize(deserializer)?
+                .map(|text| text.parse().map_err(D::Error::custom))
+                .transpose()
+        }
+    }
+
+    // Uid nie ma Display - tekstem jest sam numer
+    pub mod uid {
+        use serde::{Deserializer, Serialize, Serializer};
+        use sysinfo::Uid;
+
+        pub fn serialize<S: Serializer>(value: &Option<Uid>, serializer: S) -> Result<S::Ok, S::Error> {
+            value.as_ref().map(|uid| (**uid).to_string()).serialize(serializer)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Uid>, D::Error> {
+            super::option::deserialize(deserializer)
+        }
+    }
+}
+
+#[cfg(test)]
+impl ProcessInfo {
+    // proces z samym PID-em i nazwą - do budowania próbek w testach
+    pub fn with_name(pid: u32, name: &str) -> Self {
+        Self {
+            pid: Pid::from_u32(pid),
+            parent: None,
+            is_thread: false,
+            name: name.to_string(),
+            cpu: None,
+            rss: None,
+            read_bytes: None,
+            write_bytes: None,
+            total_read: None,
+            total_written: None,
             user: None,
+            real_user: None,
+            uid: None,
+            euid: None,
+            cmd: Vec::new(),
+            exe: None,
+            status: "Sleeping".to_string(),
+            start_time: 0,
+            run_time: 0,
+            session: None,
+            tty: None,
+            threads: None,
+            virtual_mem: None,
+            shared_mem: None,
+            anon_mem: None,
+            swap_mem: None,
+            pss: None,
         }
     }
 }
 
 // struktura przechowująca listy do generowania wykresów
-// wykorzystywane VecDeque, żeby trzymać tylko 60 ostatnich danych
-#[derive(Default)]
+// wykorzystywane VecDeque, żeby trzymać tylko `history` ostatnich danych
 pub struct ProcessInfoHistory {
     pub cpu: VecDeque<f32>,
-    pub mem_mb: VecDeque<f64>,
+    pub rss: VecDeque<u64>,
+    pub virtual_mem: VecDeque<u64>,
+    pub shared_mem: VecDeque<u64>,
+    pub anon_mem: VecDeque<u64>,
+    pub swap_mem: VecDeque<u64>,
+    pub pss: VecDeque<u64>,
     pub read_bytes: VecDeque<u64>,
     pub write_bytes: VecDeque<u64>,
     pub total_read: VecDeque<u64>,
     pub total_written: VecDeque<u64>,
+    pub last_seen: Instant, // ostatni pomiar - po zniknięciu procesu historia jest usuwana po czasie
+}
+
+impl ProcessInfoHistory {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            cpu: VecDeque::new(),
+            rss: VecDeque::new(),
+            virtual_mem: VecDeque::new(),
+            shared_mem: VecDeque::new(),
+            anon_mem: VecDeque::new(),
+            swap_mem: VecDeque::new(),
+            pss: VecDeque::new(),
+            read_bytes: VecDeque::new(),
+            write_bytes: VecDeque::new(),
+            total_read: VecDeque::new(),
+            total_written: VecDeque::new(),
+            last_seen: now,
+        }
+    }
 } 
\ No newline at end of file
@@ -0,0 +1,366 @@
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use sysinfo::Pid;
+
+use crate::utils::format_option;
+
+// pomocnicze funkcje czytające to, czego sysinfo nie udostępnia (Linux /proc i /sys)
+// na innych systemach pliki nie istnieją i wszystkie funkcje zwracają None
+
+// pola z /proc/<pid>/stat występujące po nazwie procesu (nazwa może zawierać spacje i nawiasy)
+fn stat_fields(pid: Pid) -> Option<Vec<String>> {
+    let content = fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    let rest = &content[content.rfind(')')? + 1..];
+    Some(rest.split_whitespace().map(str::to_string).collect())
+}
+
+// katalog roboczy procesu (do procesów innych użytkowników potrzebne są uprawnienia)
+pub fn read_cwd(pid: Pid) -> Option<String> {
+    fs::read_link(format!("/proc/{pid}/cwd")).ok().map(|path| path.display().to_string())
+}
+
+// terminal sterujący procesu (np. "pts/3", "tty1"), None gdy proces nie ma terminala
+pub fn read_tty(pid: Pid) -> Option<String> {
+    // po nawiasie: stan, ppid, pgrp, sesja, tty_nr
+    let tty_nr: u32 = stat_fields(pid)?.get(4)?.parse().ok()?;
+    if tty_nr == 0 {
+        return None;
+    }
+    let major = (tty_nr >> 8) & 0xfff;
+    let minor = (tty_nr & 0xff) | ((tty_nr >> 12) & 0xfff00);
+    Some(match major {
+        136..=143 => format!("pts/{}", minor + (major - 136) * 256),
+        4 if minor < 64 => format!("tty{minor}"),
+        4 => format!("ttyS{}", minor - 64),
+        _ => format!("{major}:{minor}"),
+    })
+}
+
+// pojedynczy licznik z /sys/class/net/<interfejs>/statistics
+fn net_statistic(interface: &str, name: &str) -> Option<u64> {
+    fs::read_to_string(format!("/sys/class/net/{interface}/statistics/{name}")).ok()?.trim().parse().ok()
+}
+
+// liczba odrzuconych pakietów (odebranych, wysłanych) interfejsu sieciowego
+pub fn read_net_drops(interface: &str) -> (Option<u64>, Option<u64>) {
+    (net_statistic(interface, "rx_dropped"), net_statistic(interface, "tx_dropped"))
+}
+
+// liczniki jednego urządzenia blokowego z /proc/diskstats (od startu systemu)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DiskStats {
+    pub reads: u64, // zakończone odczyty
+    pub sectors_read: u64, // odczytane sektory (po 512 bajtów)
+    pub ms_reading: u64, // czas spędzony na odczytach
+    pub writes: u64, // zakończone zapisy
+    pub sectors_written: u64,
+    pub ms_writing: u64,
+    pub ms_doing_io: u64, // czas, w którym urządzenie miało cokolwiek do zrobienia
+}
+
+// liczniki całych urządzeń (bez partycji oraz urządzeń loop i ram)
+pub fn read_diskstats() -> Vec<(String, DiskStats)> {
+    let Ok(content) = fs::read_to_string("/proc/diskstats") else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let name = *fields.get(2)?;
+            // partycje nie mają własnego katalogu w /sys/block
+            if name.starts_with("loop") || name.starts_with("ram") || !Path::new("/sys/block").join(name).exists() {
+                return None;
+            }
+            let field = |i: usize| -> Option<u64> { fields.get(i)?.parse().ok() };
+            let stats = DiskStats {
+                reads: field(3)?,
+                sectors_read: field(5)?,
+                ms_reading: field(6)?,
+                writes: field(7)?,
+                sectors_written: field(9)?,
+                ms_writing: field(10)?,
+                ms_doing_io: field(12)?,
+            };
+            Some((name.to_string(), stats))
+        })
+        .collect()
+}
+
+// wybrane pola z /proc/meminfo (w bajtach)
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct MemInfo {
+    pub total: u64,
+    pub free: u64,
+    pub available: u64,
+    pub buffers: u64,
+    pub cached: u64, // pamięć podręczna stron (razem z pamięcią współdzieloną)
+    pub shmem: u64, // pamięć współdzielona (tmpfs, shm)
+    pub slab: u64, // struktury jądra
+    pub dirty: u64, // strony czekające na zapis
+    pub writeback: u64, // strony właśnie zapisywane
+    pub swap_total: u64,
+    pub swap_free: u64,
+}
+
+impl MemInfo {
+    pub fn swap_used(&self) -> u64 {
+        self.swap_total.saturating_sub(self.swap_free)
+    }
+
+    // pamięć zajęta przez bufory i pamięć podręczną (do odzyskania w razie potrzeby)
+    pub fn buffers_cache(&self) -> u64 {
+        self.buffers + self.cached
+    }
+}
+
+// odczyt /proc/meminfo, None gdy pliku nie ma
+pub fn read_meminfo() -> Option<MemInfo> {
+    let content = fs::read_to_string("/proc/meminfo").ok()?;
+    let mut info = MemInfo::default();
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let field = match key {
+            "MemTotal" => &mut info.total,
+            "MemFree" => &mut info.free,
+            "MemAvailable" => &mut info.available,
+            "Buffers" => &mut info.buffers,
+            "Cached" => &mut info.cached,
+            "Shmem" => &mut info.shmem,
+            "Slab" => &mut info.slab,
+            "Dirty" => &mut info.dirty,
+            "Writeback" => &mut info.writeback,
+            "SwapTotal" => &mut info.swap_total,
+            "SwapFree" => &mut info.swap_free,
+            _ => continue,
+        };
+        // wartości podane są w kB
+        if let Ok(kb) = value.trim().trim_end_matches("kB").trim().parse::<u64>() {
+            *field = kb * 1024;
+        }
+    }
+    Some(info)
+}
+
+// jedna linia z /proc/pressure/<zasób> - procent czasu, w którym zadania czekały na zasób
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct PressureStall {
+    pub avg10: f64, // średnia z 10 sekund
+    pub avg60: f64,
+    pub avg300: f64,
+    pub total_us: u64, // łączny czas oczekiwania w mikrosekundach
+}
+
+// "some" - czekało choć jedno zadanie, "full" - czekały wszystkie (brak na starszych jądrach)
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Pressure {
+    pub some: PressureStall,
+    pub full: Option<PressureStall>,
+}
+
+// odczyt /proc/pressure/<zasób>, None gdy jądro nie ma PSI (lub jest wyłączone przez psi=0)
+pub fn read_pressure(resource: &str) -> Option<Pressure> {
+    let content = fs::read_to_string(format!("/proc/pressure/{resource}")).ok()?;
+    let parse_line = |line: &str| -> Option<PressureStall> {
+        let mut stall = PressureStall::default();
+        for item in line.split_whitespace().skip(1) {
+            let (key, value) = item.split_once('=')?;
+            match key {
+                "avg10" => stall.avg10 = value.parse().ok()?,
+                "avg60" => stall.avg60 = value.parse().ok()?,
+                "avg300" => stall.avg300 = value.parse().ok()?,
+                "total" => stall.total_us = value.parse().ok()?,
+                _ => {}
+            }
+        }
+        Some(stall)
+    };
+    let mut some = None;
+    let mut full = None;
+    for line in content.lines() {
+        if line.starts_with("some ") {
+            some = parse_line(line);
+        } else if line.starts_with("full ") {
+            full = parse_line(line);
+        }
+    }
+    Some(Pressure { some: some?, full })
+}
+
+// czasy jednego procesora z /proc/stat (w jednostkach USER_HZ, od startu systemu)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CpuTimes {
+    pub user: u64,
+    pub nice: u64,
+    pub system: u64,
+    pub idle: u64,
+    pub iowait: u64,
+    pub irq: u64,
+    pub softirq: u64,
+    pub steal: u64, // czas zabrany przez hiperwizor innym maszynom wirtualnym
+}
+
+// czasy poszczególnych procesorów ("cpu0", "cpu1", ...), bez zbiorczej linii "cpu"
+pub fn read_cpu_times() -> Vec<(String, CpuTimes)> {
+    let Ok(content) = fs::read_to_string("/proc/stat") else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .filter(|line| line.starts_with("cpu") && !line.starts_with("cpu "))
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            // starsze jądra nie mają wszystkich kolumn
+            let field = |i: usize| -> u64 { fields.get(i).and_then(|v| v.parse().ok()).unwrap_or(0) };
+            let times = CpuTimes {
+                user: field(1),
+                nice: field(2),
+                system: field(3),
+                idle: field(4),
+                iowait: field(5),
+                irq: field(6),
+                softirq: field(7),
+                steal: field(8),
+            };
+            Some((fields.first()?.to_string(), times))
+        })
+        .collect()
+}
+
+// katalog procesora logicznego w sysfs
+fn cpu_sysfs(cpu: usize, file: &str) -> Option<String> {
+    let content = fs::read_to_string(format!("/sys/devices/system/cpu/cpu{cpu}/{file}")).ok()?;
+    Some(content.trim().to_string())
+}
+
+// częstotliwość (w kHz) i gubernator z /sys/devices/system/cpu/cpuN/cpufreq
+// na maszynach wirtualnych cpufreq często nie istnieje
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CpuFrequency {
+    pub current: Option<u64>,
+    pub min: Option<u64>,
+    pub max: Option<u64>,
+    pub governor: Option<String>,
+}
+
+// zakres i gubernator - zmieniają się rzadko, więc kolektor czyta je co kilkanaście odświeżeń
+pub fn read_cpu_frequency(cpu: usize) -> CpuFrequency {
+    let khz = |file: &str| cpu_sysfs(cpu, file)?.parse().ok();
+    CpuFrequency {
+        current: read_cpu_current_frequency(cpu),
+        min: khz("cpufreq/cpuinfo_min_freq"),
+        max: khz("cpufreq/cpuinfo_max_freq"),
+        governor: cpu_sysfs(cpu, "cpufreq/scaling_governor"),
+    }
+}
+
+// sama bieżąca częstotliwość - czytana przy każdym odświeżeniu
+pub fn read_cpu_current_frequency(cpu: usize) -> Option<u64> {
+    cpu_sysfs(cpu, "cpufreq/scaling_cur_freq")?.parse().ok()
+}
+
+// położenie procesora logicznego - gniazdo i rdzeń fizyczny (wątki SMT mają ten sam rdzeń)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct CpuTopology {
+    pub package: u32,
+    pub core: u32,
+}
+
+pub fn read_cpu_topology(cpu: usize) -> Option<CpuTopology> {
+    Some(CpuTopology {
+        package: cpu_sysfs(cpu, "topology/physical_package_id")?.parse().ok()?,
+        core: cpu_sysfs(cpu, "topology/core_id")?.parse().ok()?,
+    })
+}
+
+// pamięć podręczna procesora, np. ("L1d", "48K")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheInfo {
+    pub name: String,
+    pub size: String,
+}
+
+// model procesora i jego pamięci podręczne
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CpuModel {
+    pub name: Option<String>,
+    pub caches: Vec<CacheInfo>,
+}
+
+// model z /proc/cpuinfo, pamięci podręczne z sysfs (cpu0/cache/indexN),
+// a gdy ich brak - pole "cache size" z /proc/cpuinfo
+pub fn read_cpu_model() -> CpuModel {
+    let cpuinfo = fs::read_to_string("/proc/cpuinfo").unwrap_or_default();
+    let field = |key: &str| {
+        cpuinfo.lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            (name.trim() == key).then(|| value.trim().to_string())
+        })
+    };
+
+    let mut caches = Vec::new();
+    for index in 0.. {
+        let cache = |file: &str| cpu_sysfs(0, &format!("cache/index{index}/{file}"));
+        let Some(level) = cache("level") else {
+            break;
+        };
+        let suffix = match cache("type").as_deref() {
+            Some("Data") => "d",
+            Some("Instruction") => "i",
+            _ => "",
+        };
+        caches.push(CacheInfo { name: format!("L{level}{suffix}"), size: format_option(cache("size")) });
+    }
+    if caches.is_empty()
+        && let Some(size) = field("cache size")
+    {
+        caches.push(CacheInfo { name: "Cache".to_string(), size });
+    }
+
+    CpuModel { name: field("model name").or_else(|| field("Hardware")), caches }
+}
+
+// rozbicie pamięci procesu z /proc/<pid>/status (w bajtach)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StatusMemory {
+    pub shared: u64, // strony plików i pamięci współdzielonej (RssFile + RssShmem, jak SHR w top)
+    pub anon: u64, // pamięć anonimowa (RssAnon)
+    pub swap: u64, // wyrzucone do swapu (VmSwap)
+}
+
+// None dla wątków jądra (nie mają tych pól) i procesów, które już się zakończyły
+pub fn read_status_memory(pid: Pid) -> Option<StatusMemory> {
+    let content = fs::read_to_string(format!("/proc/{pid}/status")).ok()?;
+    let mut memory = StatusMemory::default();
+    let mut found = false;
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let field = match key {
+            "RssFile" | "RssShmem" => &mut memory.shared,
+            "RssAnon" => &mut memory.anon,
+            "VmSwap" => &mut memory.swap,
+            _ => continue,
+        };
+        // wartości podane są w kB
+        if let Ok(kb) = value.trim().trim_end_matches("kB").trim().parse::<u64>() {
+            *field += kb * 1024;
+            found = true;
+        }
+    }
+    found.then_some(memory)
+}
+
+// PSS (pamięć współdzielona podzielona między procesy, które ją mapują) z /proc/<pid>/smaps_rollup
+// odczyt jest kosztowny (jądro przechodzi całą przestrzeń adresową), a pliki cudzych procesów wymagają uprawnień
+pub fn read_pss(pid: Pid) -> Option<u64> {
+    let content = fs::read_to_string(format!("/proc/{pid}/smaps_rollup")).ok()?;
+    let line = content.lines().find(|line| line.starts_with("Pss:"))?;
+    let kb: u64 = line["Pss:".len()..].trim().trim_end_matches("kB").trim().parse().ok()?;
+    Some(kb * 1024)
+}
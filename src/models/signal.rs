@@ -0,0 +1,81 @@
+use sysinfo::{Pid, ProcessRefreshKind, System};
+
+use super::ProcessKey;
+
+// sygnały, które można wysłać do procesu z poziomu tabeli
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignalKind {
+    Term,
+    Kill,
+    Hup,
+    Int,
+    Stop,
+    Cont,
+    Usr1,
+    Usr2,
+}
+
+impl SignalKind {
+    // kolejność w okienku wyboru sygnału
+    pub const ALL: [SignalKind; 8] = [
+        SignalKind::Term,
+        SignalKind::Kill,
+        SignalKind::Hup,
+        SignalKind::Int,
+        SignalKind::Stop,
+        SignalKind::Cont,
+        SignalKind::Usr1,
+        SignalKind::Usr2,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SignalKind::Term => "SIGTERM",
+            SignalKind::Kill => "SIGKILL",
+            SignalKind::Hup => "SIGHUP",
+            SignalKind::Int => "SIGINT",
+            SignalKind::Stop => "SIGSTOP",
+            SignalKind::Cont => "SIGCONT",
+            SignalKind::Usr1 => "SIGUSR1",
+            SignalKind::Usr2 => "SIGUSR2",
+        }
+    }
+
+    #[cfg(unix)]
+    fn number(&self) -> libc::c_int {
+        match self {
+            SignalKind::Term => libc::SIGTERM,
+            SignalKind::Kill => libc::SIGKILL,
+            SignalKind::Hup => libc::SIGHUP,
+            SignalKind::Int => libc::SIGINT,
+            SignalKind::Stop => libc::SIGSTOP,
+            SignalKind::Cont => libc::SIGCONT,
+            SignalKind::Usr1 => libc::SIGUSR1,
+            SignalKind::Usr2 => libc::SIGUSR2,
+        }
+    }
+}
+
+// czy pod PID-em z klucza nadal działa ten sam proces (ten sam czas startu)
+pub fn is_running(key: ProcessKey) -> bool {
+    let mut sys = System::new();
+    sys.refresh_process_specifics(key.pid, ProcessRefreshKind::new())
+        && sys.process(key.pid).is_some_and(|process| process.start_time() == key.start_time)
+}
+
+// wysłanie sygnału - w razie błędu zwraca opis z systemu (np. brak uprawnień)
+#[cfg(unix)]
+pub fn send_signal(pid: Pid, signal: SignalKind) -> Result<(), std::io::Error> {
+    // SAFETY: kill nie operuje na pamięci programu, jedynie przekazuje sygnał do jądra
+    let result = unsafe { libc::kill(pid.as_u32() as libc::pid_t, signal.number()) };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+#[cfg(not(unix))]
+pub fn send_signal(_pid: Pid, _signal: SignalKind) -> Result<(), std::io::Error> {
+    Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "signals are not supported on this platform"))
+}
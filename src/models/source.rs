@@ -0,0 +1,168 @@
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use color_eyre::{Result, eyre::{WrapErr, eyre}};
+
+use super::{GeneralInfo, ProcessInfo};
+
+// pojedyncza paczka danych o procesach i systemie
+pub type Snapshot = (Vec<ProcessInfo>, GeneralInfo);
+
+// źródło danych dla aplikacji - na żywo (Collector) albo z gotowego scenariusza (ScriptedSource)
+pub trait DataSource {
+    // pobranie nowej próbki
+    fn refresh(&mut self);
+    // dane o procesach z ostatniej próbki
+    fn processes(&mut self) -> Vec<ProcessInfo>;
+    // dane o całym systemie z ostatniej próbki
+    fn general_info(&mut self) -> GeneralInfo;
+
+    // odświeżenie i zebranie świeżych danych o procesach i systemie
+    fn collect(&mut self) -> Snapshot {
+        self.refresh();
+        (self.processes(), self.general_info())
+    }
+}
+
+// deterministyczne źródło danych odtwarzające zadane z góry próbki
+// (nagranie z `--record` odtwarzane przez `--replay` albo scenariusz w testach)
+#[derive(Debug, Default)]
+pub struct ScriptedSource {
+    frames: Vec<Snapshot>, // kolejne próbki
+    position: Option<usize>, // indeks aktualnej próbki (None - przed pierwszym odświeżeniem)
+}
+
+impl ScriptedSource {
+    pub fn new(frames: Vec<Snapshot>) -> Self {
+        Self { frames, position: None }
+    }
+
+    // wczytanie nagrania z `--record` - jedna próbka (JSON) w każdej linii
+    pub fn load(path: &Path) -> Result<Self> {
+        let file = File::open(path).wrap_err_with(|| format!("cannot open recording {}", path.display()))?;
+        let mut frames = Vec::new();
+        for (number, line) in BufReader::new(file).lines().enumerate() {
+            let line = line.wrap_err_with(|| format!("cannot read recording {}", path.display()))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let frame = serde_json::from_str(&line)
+                .wrap_err_with(|| format!("invalid sample on line {} of {}", number + 1, path.display()))?;
+            frames.push(frame);
+        }
+        if frames.is_empty() {
+            return Err(eyre!("recording {} has no samples", path.display()));
+        }
+        Ok(Self::new(frames))
+    }
+
+    // dopisanie kolejnej próbki na koniec scenariusza
+    #[cfg(test)]
+    pub fn push_frame(&mut self, processes: Vec<ProcessInfo>, general_info: GeneralInfo) {
+        self.frames.push((processes, general_info));
+    }
+
+    // czy scenariusz doszedł do ostatniej próbki
+    #[cfg(test)]
+    pub fn is_finished(&self) -> bool {
+        self.position.is_some_and(|i| i + 1 >= self.frames.len())
+    }
+
+    fn current(&self) -> Option<&Snapshot> {
+        self.position.and_then(|i| self.frames.get(i))
+    }
+}
+
+impl DataSource for ScriptedSource {
+    // przejście do następnej próbki - po końcu scenariusza zostajemy na ostatniej
+    fn refresh(&mut self) {
+        self.position = match self.position {
+            None => Some(0),
+            Some(i) if i + 1 < self.frames.len() => Some(i + 1),
+            Some(i) => Some(i),
+        };
+    }
+
+    fn processes(&mut self) -> Vec<ProcessInfo> {
+        self.current().map(|(processes, _)| processes.clone()).unwrap_or_default()
+    }
+
+    fn general_info(&mut self) -> GeneralInfo {
+        self.current().map(|(_, general_info)| general_info.clone()).unwrap_or_default()
+    }
+}
+
+// źródło zapisujące każdą zebraną próbkę do pliku (`--record`), do późniejszego `--replay`
+pub struct RecordingSource<S: DataSource> {
+    source: S,
+    file: File,
+}
+
+impl<S: DataSource> RecordingSource<S> {
+    // nagranie dopisuję na koniec istniejącego pliku
+    pub fn create(source: S, path: &Path) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .wrap_err_with(|| format!("cannot open recording {}", path.display()))?;
+        Ok(Self { source, file })
+    }
+}
+
+impl<S: DataSource> DataSource for RecordingSource<S> {
+    fn refresh(&mut self) {
+        self.source.refresh();
+    }
+
+    fn processes(&mut self) -> Vec<ProcessInfo> {
+        self.source.processes()
+    }
+
+    fn general_info(&mut self) -> GeneralInfo {
+        self.source.general_info()
+    }
+
+    fn collect(&mut self) -> Snapshot {
+        let snapshot = self.source.collect();
+        // błąd zapisu (np. pełny dysk) nie może przerwać monitorowania - próbka po prostu przepada
+        if let Ok(mut line) = serde_json::to_string(&snapshot) {
+            line.push('\n');
+            let _ = self.file.write_all(line.as_bytes());
+        }
+        snapshot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sysinfo::Pid;
+
+    use super::*;
+
+    #[test]
+    fn recording_replays_the_same_samples() {
+        let path = std::env::temp_dir().join(format!("monitor-recording-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut parent = ProcessInfo::with_name(1, "init");
+        parent.uid = Some("0".parse().unwrap());
+        let mut child = ProcessInfo::with_name(42, "shell");
+        child.parent = Some(parent.pid);
+        let scripted = ScriptedSource::new(vec![
+            (vec![parent], GeneralInfo::default()),
+            (vec![child], GeneralInfo::default()),
+        ]);
+        let mut recording = RecordingSource::create(scripted, &path).unwrap();
+        recording.collect();
+        recording.collect();
+
+        let mut replay = ScriptedSource::load(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        let (first, _) = replay.collect();
+        assert_eq!(first[0].name, "init");
+        assert_eq!(first[0].uid, Some("0".parse().unwrap()));
+        let (second, _) = replay.collect();
+        assert_eq!((second[0].pid, second[0].parent), (Pid::from_u32(42), Some(Pid::from_u32(1))));
+        assert!(replay.is_finished());
+    }
+}
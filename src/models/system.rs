@@ -1,14 +1,79 @@
-use sysinfo::{Disks, System};
+use std::collections::VecDeque;
+use serde::{Deserialize, Serialize};
+use sysinfo::{Disks, Networks, ProcessStatus, System, ThreadKind};
+
+use super::{
+    read_meminfo, read_net_drops, read_pressure, CpuFrequency, CpuModel, CpuTimes, CpuTopology, DiskStats, MemInfo,
+    Pressure,
+};
 
 // struktura przechowująca zużycie wątku procesora
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CpuInfo {
     pub name: String,
     pub usage: Option<f32>,
+    pub breakdown: Option<CpuBreakdown>, // podział czasu z /proc/stat (tylko Linux)
+    pub frequency: CpuFrequency,
+    pub topology: Option<CpuTopology>, // gniazdo i rdzeń fizyczny
+}
+
+impl CpuInfo {
+    // numer procesora logicznego z nazwy ("cpu3" -> 3)
+    pub fn index(&self) -> Option<usize> {
+        self.name.strip_prefix("cpu")?.parse().ok()
+    }
+}
+
+// podział czasu procesora między dwoma odczytami /proc/stat, w procentach
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct CpuBreakdown {
+    pub user: f64,
+    pub nice: f64,
+    pub system: f64,
+    pub idle: f64,
+    pub iowait: f64,
+    pub irq: f64,
+    pub softirq: f64,
+    pub steal: f64,
+}
+
+impl CpuBreakdown {
+    // nazwy części zajętego czasu, w kolejności z `parts`
+    pub const PART_NAMES: [&str; 7] = ["user", "nice", "system", "iowait", "irq", "softirq", "steal"];
+
+    pub fn from_delta(previous: &CpuTimes, current: &CpuTimes) -> Self {
+        let delta = |f: fn(&CpuTimes) -> u64| f(current).saturating_sub(f(previous)) as f64;
+        let parts = [
+            delta(|t| t.user),
+            delta(|t| t.nice),
+            delta(|t| t.system),
+            delta(|t| t.idle),
+            delta(|t| t.iowait),
+            delta(|t| t.irq),
+            delta(|t| t.softirq),
+            delta(|t| t.steal),
+        ];
+        let total: f64 = parts.iter().sum();
+        let [user, nice, system, idle, iowait, irq, softirq, steal] =
+            parts.map(|part| if total > 0.0 { part / total * 100.0 } else { 0.0 });
+        Self { user, nice, system, idle, iowait, irq, softirq, steal }
+    }
+
+    // zajęty czas (wszystko poza idle), w kolejności z PART_NAMES
+    pub fn parts(&self) -> [f64; 7] {
+        [self.user, self.nice, self.system, self.iowait, self.irq, self.softirq, self.steal]
+    }
+}
+
+// historia jednego rdzenia - zużycie i jego podział
+#[derive(Default)]
+pub struct CoreHistory {
+    pub usage: VecDeque<f64>,
+    pub parts: [VecDeque<f64>; 7], // w kolejności z CpuBreakdown::PART_NAMES
 }
 
 // struktura reprezentująca inforamcje o danym dysku
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiskInfo {
     pub name: Option<String>,
     pub mount_point: Option<String>,
@@ -16,33 +81,205 @@ pub struct DiskInfo {
     pub available_space: Option<u64>,
 }
 
+// struktura reprezentująca dane interfejsu sieciowego
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkInfo {
+    pub name: String,
+    pub rx_rate: f64, // odbierane bajty na sekundę
+    pub tx_rate: f64, // wysyłane bajty na sekundę
+    pub total_rx: u64,
+    pub total_tx: u64,
+    pub rx_packets: u64,
+    pub tx_packets: u64,
+    pub rx_errors: u64,
+    pub tx_errors: u64,
+    pub rx_dropped: Option<u64>, // z /sys/class/net (sysinfo ich nie podaje)
+    pub tx_dropped: Option<u64>,
+}
+
+// obciążenie urządzenia blokowego, liczone z przyrostów /proc/diskstats
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockDeviceInfo {
+    pub name: String,
+    pub read_rate: f64, // bajty na sekundę
+    pub write_rate: f64,
+    pub iops: f64, // operacje (odczyty + zapisy) na sekundę
+    pub await_ms: Option<f64>, // średni czas operacji, brak gdy nie było operacji
+    pub util: f64, // procent czasu, w którym urządzenie było zajęte
+}
+
+impl BlockDeviceInfo {
+    // wartości z dwóch odczytów oddalonych o `elapsed` sekund
+    pub fn from_delta(name: &str, previous: &DiskStats, current: &DiskStats, elapsed: f64) -> Self {
+        const SECTOR_SIZE: f64 = 512.0;
+        let delta = |f: fn(&DiskStats) -> u64| f(current).saturating_sub(f(previous)) as f64;
+        let per_second = |value: f64| if elapsed > 0.0 { value / elapsed } else { 0.0 };
+        let operations = delta(|s| s.reads) + delta(|s| s.writes);
+        let busy_ms = delta(|s| s.ms_reading) + delta(|s| s.ms_writing);
+        Self {
+            name: name.to_string(),
+            read_rate: per_second(delta(|s| s.sectors_read) * SECTOR_SIZE),
+            write_rate: per_second(delta(|s| s.sectors_written) * SECTOR_SIZE),
+            iops: per_second(operations),
+            await_ms: (operations > 0.0).then(|| busy_ms / operations),
+            util: (per_second(delta(|s| s.ms_doing_io)) / 10.0).min(100.0),
+        }
+    }
+}
+
+// historia obciążenia urządzenia blokowego
+#[derive(Default)]
+pub struct BlockDeviceHistory {
+    pub read: VecDeque<f64>,
+    pub write: VecDeque<f64>,
+    pub iops: VecDeque<f64>,
+    pub await_ms: VecDeque<f64>,
+    pub util: VecDeque<f64>,
+}
+
+// zasoby, dla których jądro podaje PSI (Pressure Stall Information)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PressureResource {
+    Cpu,
+    Memory,
+    Io,
+}
+
+impl PressureResource {
+    pub const ALL: [PressureResource; 3] = [PressureResource::Cpu, PressureResource::Memory, PressureResource::Io];
+
+    // nazwa pliku w /proc/pressure (i w konfiguracji)
+    pub fn name(&self) -> &'static str {
+        match self {
+            PressureResource::Cpu => "cpu",
+            PressureResource::Memory => "memory",
+            PressureResource::Io => "io",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<PressureResource> {
+        Self::ALL.iter().copied().find(|resource| resource.name() == name)
+    }
+}
+
+// historia presji zasobu (avg10 w procentach)
+#[derive(Default)]
+pub struct PressureHistory {
+    pub some: VecDeque<f64>,
+    pub full: VecDeque<f64>,
+}
+
+// liczba zadań (procesów albo wątków) w poszczególnych stanach
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct TaskCounts {
+    pub total: usize,
+    pub running: usize,
+    pub sleeping: usize,
+    pub stopped: usize,
+    pub zombie: usize,
+}
+
+impl TaskCounts {
+    fn add(&mut self, status: ProcessStatus) {
+        self.total += 1;
+        match status {
+            ProcessStatus::Run => self.running += 1,
+            ProcessStatus::Sleep | ProcessStatus::Idle | ProcessStatus::UninterruptibleDiskSleep => self.sleeping += 1,
+            ProcessStatus::Stop | ProcessStatus::Tracing => self.stopped += 1,
+            ProcessStatus::Zombie => self.zombie += 1,
+            _ => {}
+        }
+    }
+}
+
+// dane identyfikujące maszynę i jej obciążenie (do paska nagłówka)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HostInfo {
+    pub hostname: Option<String>,
+    pub os: Option<String>, // nazwa i wersja systemu, np. "Linux 24.04 Ubuntu"
+    pub kernel: Option<String>,
+    pub uptime: u64, // w sekundach
+    pub load_average: [f64; 3], // średnie z 1, 5 i 15 minut
+    pub processes: TaskCounts,
+    pub threads: TaskCounts, // wszystkie wątki, razem z głównymi wątkami procesów
+}
+
+impl HostInfo {
+    pub fn get_host_data(sys: &System) -> HostInfo {
+        let mut processes = TaskCounts::default();
+        let mut threads = TaskCounts::default();
+        for process in sys.processes().values() {
+            threads.add(process.status());
+            if process.thread_kind() != Some(ThreadKind::Userland) {
+                processes.add(process.status());
+            }
+        }
+        let load = System::load_average();
+        HostInfo {
+            hostname: System::host_name(),
+            os: System::long_os_version(),
+            kernel: System::kernel_version(),
+            uptime: System::uptime(),
+            load_average: [load.one, load.five, load.fifteen],
+            processes,
+            threads,
+        }
+    }
+}
+
+// historia zajętości pamięci w procentach
+#[derive(Default)]
+pub struct MemoryHistory {
+    pub used: VecDeque<f64>, // pamięć faktycznie używana (procent RAM)
+    pub buffers_cache: VecDeque<f64>, // bufory i pamięć podręczna (procent RAM)
+    pub swap: VecDeque<f64>, // zajęty swap (procent swapu)
+}
+
+// historia przepustowości interfejsu (bajty na sekundę)
+#[derive(Default)]
+pub struct NetworkHistory {
+    pub rx: VecDeque<f64>,
+    pub tx: VecDeque<f64>,
+}
+
 // struktura reprezentująca globalne inforamcje systemowe
-#[derive(Debug)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct GeneralInfo {
     pub ram_total_memory: Option<u64>,
     pub ram_available_memory: Option<u64>,
     pub ram_used_memor: Option<u64>,
+    pub memory: Option<MemInfo>, // szczegóły z /proc/meminfo (swap, bufory, cache, ...)
     pub cpu_usage_tab: Vec<CpuInfo>,
+    pub cpu_model: CpuModel,
     pub disk_tab: Vec<DiskInfo>,
+    pub network_tab: Vec<NetworkInfo>,
+    pub block_device_tab: Vec<BlockDeviceInfo>,
+    pub host: HostInfo,
+    pub pressure: Vec<(PressureResource, Pressure)>, // pusta, gdy PSI jest niedostępne
 }
 
 impl GeneralInfo {
     // funkcja do pobierania danych o zasobach sprzętu
-    pub fn get_general_data(sys: System) -> GeneralInfo {
+    // `elapsed` - sekundy od poprzedniego pomiaru (do liczenia przepustowości sieci)
+    pub fn get_general_data(sys: &System, disks: &Disks, networks: &Networks, elapsed: f64) -> GeneralInfo {
         // CPU usage
         let mut cpu_usage_tab = Vec::new();
         for cpu in sys.cpus().iter() {
+            // częstotliwość podana przez sysinfo (w MHz) - kolektor zastępuje ją odczytem z cpufreq
+            let current = (cpu.frequency() > 0).then(|| cpu.frequency() * 1000);
             cpu_usage_tab.push(CpuInfo {
                 name: cpu.name().to_string(),
                 usage: Some(cpu.cpu_usage()),
+                breakdown: None,
+                frequency: CpuFrequency { current, ..CpuFrequency::default() },
+                topology: None,
             });
         }
 
         // Dyski
-        let disks = Disks::new_with_refreshed_list();
         let mut disk_tab = Vec::new();
 
-        for disk in &disks {
+        for disk in disks {
             let name = disk.name().to_str().map(|s| s.to_string());
             let mount_point = disk.mount_point().to_str().map(|s| s.to_string());
             let disk_info = DiskInfo {
@@ -53,13 +290,45 @@ impl GeneralInfo {
             };
             disk_tab.push(disk_info);
         }
+        // Sieć - interfejsy posortowane po nazwie, żeby wiersze nie skakały
+        let rate = |bytes: u64| if elapsed > 0.0 { bytes as f64 / elapsed } else { 0.0 };
+        let mut network_tab: Vec<NetworkInfo> = networks
+            .iter()
+            .map(|(name, data)| {
+                let (rx_dropped, tx_dropped) = read_net_drops(name);
+                NetworkInfo {
+                    name: name.clone(),
+                    rx_rate: rate(data.received()),
+                    tx_rate: rate(data.transmitted()),
+                    total_rx: data.total_received(),
+                    total_tx: data.total_transmitted(),
+                    rx_packets: data.total_packets_received(),
+                    tx_packets: data.total_packets_transmitted(),
+                    rx_errors: data.total_errors_on_received(),
+                    tx_errors: data.total_errors_on_transmitted(),
+                    rx_dropped,
+                    tx_dropped,
+                }
+            })
+            .collect();
+        network_tab.sort_by(|a, b| a.name.cmp(&b.name));
+
         // zwrócenie stuktury (siebie)
         Self {
             ram_total_memory: Some(sys.total_memory()),
             ram_available_memory: Some(sys.available_memory()),
             ram_used_memor: Some(sys.used_memory()),
+            memory: read_meminfo(),
             cpu_usage_tab,
+            cpu_model: CpuModel::default(),
             disk_tab,
+            network_tab,
+            block_device_tab: Vec::new(),
+            host: HostInfo::get_host_data(sys),
+            pressure: PressureResource::ALL
+                .into_iter()
+                .filter_map(|resource| Some((resource, read_pressure(resource.name())?)))
+                .collect(),
         }
     }
 
@@ -1,4 +1,5 @@
-// enum ProcessName reprezentuje nazwy danych zbieranych o procesie
+// enum ProcessName reprezentuje nazwy danych zbieranych o procesie (kolumny tabeli)
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ProcessName {
     PID,
@@ -10,11 +11,23 @@ pub enum ProcessName {
     TOTAL_READ,
     TOTAL_WRITTEN,
     USER,
+    PPID,
+    STATE,
+    THREADS,
+    VIRT,
+    SHARED,
+    ANON,
+    SWAP,
+    PSS,
+    START,
+    TIME,
+    TTY,
+    COMMAND,
 }
 
 impl ProcessName {
     // tablica wszystkich nazw
-    pub const ALL: [ProcessName; 9] = [
+    pub const ALL: [ProcessName; 21] = [
         ProcessName::PID,
         ProcessName::NAME,
         ProcessName::CPU,
@@ -24,55 +37,122 @@ impl ProcessName {
         ProcessName::TOTAL_READ,
         ProcessName::TOTAL_WRITTEN,
         ProcessName::USER,
+        ProcessName::PPID,
+        ProcessName::STATE,
+        ProcessName::THREADS,
+        ProcessName::VIRT,
+        ProcessName::SHARED,
+        ProcessName::ANON,
+        ProcessName::SWAP,
+        ProcessName::PSS,
+        ProcessName::START,
+        ProcessName::TIME,
+        ProcessName::TTY,
+        ProcessName::COMMAND,
     ];
-    // poruszanie się do przodu po tablicy
-    pub fn next(&self) -> ProcessName {
-        let i = Self::ALL.iter().position(|x| x == self).unwrap();
-        if i != (Self::ALL.len() - 2) {
-            Self::ALL[i + 1]
-        } else {
-            Self::ALL[i]
+    // kolumny widoczne, gdy konfiguracja nie mówi inaczej
+    pub const DEFAULT_COLUMNS: [ProcessName; 9] = [
+        ProcessName::PID,
+        ProcessName::NAME,
+        ProcessName::CPU,
+        ProcessName::MEM,
+        ProcessName::READ,
+        ProcessName::WRITE,
+        ProcessName::TOTAL_READ,
+        ProcessName::TOTAL_WRITTEN,
+        ProcessName::USER,
+    ];
+    // oznaczenie kolumny w nagłówku tabeli
+    pub fn label(&self) -> &'static str {
+        match self {
+            ProcessName::PID => "PID",
+            ProcessName::NAME => "Name",
+            ProcessName::CPU => "CPU %",
+            ProcessName::MEM => "RSS",
+            ProcessName::READ => "R",
+            ProcessName::WRITE => "W",
+            ProcessName::TOTAL_READ => "T.Read",
+            ProcessName::TOTAL_WRITTEN => "T.Write",
+            ProcessName::USER => "User",
+            ProcessName::PPID => "PPID",
+            ProcessName::STATE => "State",
+            ProcessName::THREADS => "Thr",
+            ProcessName::VIRT => "Virt",
+            ProcessName::SHARED => "Shr",
+            ProcessName::ANON => "Anon",
+            ProcessName::SWAP => "Swap",
+            ProcessName::PSS => "PSS",
+            ProcessName::START => "Start",
+            ProcessName::TIME => "Time",
+            ProcessName::TTY => "TTY",
+            ProcessName::COMMAND => "Command",
+        }
+    }
+    // nazwa pola używana w filtrach (np. `cpu > 5`) i w pliku konfiguracyjnym
+    pub fn field_name(&self) -> &'static str {
+        match self {
+            ProcessName::PID => "pid",
+            ProcessName::NAME => "name",
+            ProcessName::CPU => "cpu",
+            ProcessName::MEM => "mem",
+            ProcessName::READ => "read",
+            ProcessName::WRITE => "write",
+            ProcessName::TOTAL_READ => "total_read",
+            ProcessName::TOTAL_WRITTEN => "total_written",
+            ProcessName::USER => "user",
+            ProcessName::PPID => "ppid",
+            ProcessName::STATE => "state",
+            ProcessName::THREADS => "threads",
+            ProcessName::VIRT => "virt",
+            ProcessName::SHARED => "shared",
+            ProcessName::ANON => "anon",
+            ProcessName::SWAP => "swap",
+            ProcessName::PSS => "pss",
+            ProcessName::START => "start",
+            ProcessName::TIME => "time",
+            ProcessName::TTY => "tty",
+            ProcessName::COMMAND => "command",
         }
     }
-    // poruszanie się do tyłu
-    pub fn prev(&self) -> ProcessName {
-        let i = Self::ALL.iter().position(|x| x == self).unwrap();
-        if i != 0 {
-            Self::ALL[i - 1]
-        } else {
-            Self::ALL[i]
+    // odczytanie pola z nazwy (bez rozróżniania wielkości liter), "rss" to inna nazwa "mem"
+    pub fn from_field_name(name: &str) -> Option<ProcessName> {
+        if name.eq_ignore_ascii_case("rss") {
+            return Some(ProcessName::MEM);
         }
+        Self::ALL.iter().copied().find(|field| field.field_name().eq_ignore_ascii_case(name))
     }
-    // podbieranie indeksu (przydatne przy określaniu, która to kolumna)
-    pub fn get_index(&self) -> usize {
-        Self::ALL.iter().position(|x| x == self).unwrap()
+    // czy kolumna to pamięć procesu w bajtach
+    pub fn is_memory(&self) -> bool {
+        matches!(
+            self,
+            ProcessName::MEM | ProcessName::VIRT | ProcessName::SHARED | ProcessName::ANON | ProcessName::SWAP | ProcessName::PSS
+        )
     }
-    // pobieranie nazwy z indeksu (przydatne przy posiadaniu numeru kolumny)
-    pub fn get_name(i: usize) -> ProcessName {
-        Self::ALL[i]
+    // czy wartości kolumny są tekstowe (a nie liczbowe)
+    pub fn is_text(&self) -> bool {
+        matches!(
+            self,
+            ProcessName::NAME | ProcessName::USER | ProcessName::STATE | ProcessName::TTY | ProcessName::COMMAND
+        )
     }
 }
 
 // przydatne stałe
-pub const MAX_LEN: usize = 60;
-pub const CHART_RANGE: [f64; 2] = [0.0, 60.0];
-pub const INTERVAL: u64 = 500;
+// wartości domyślne (nadpisywane przez plik konfiguracyjny)
+pub const DEFAULT_HISTORY_LEN: usize = 60;
+pub const DEFAULT_INTERVAL: u64 = 500;
+pub const DEFAULT_HISTORY_RETENTION: u64 = 60; // w sekundach
 pub const ITEM_HEIGHT: u16 = 1;
 
-// oznacznie kolumn
-pub const COLUMN_LABEL: [&str; 8] = [
-    "PID",
-    "Name",
-    "CPU %",
-    "Mem",
-    "R",
-    "W",
-    "T.Read",
-    "T.Write",
-];
+// wysokość panelu pamięci (6 wierszy i ramka)
+pub const MEMORY_PANEL_HEIGHT: u16 = 8;
+
+// maksymalna szerokość kolumny z linią poleceń
+pub const COMMAND_WIDTH: usize = 60;
 
 // instrukcja obsługi
-pub const INFO_TEXT: [&str; 2] = [
-    "(Esc) quit | (↑) move up | (↓) move down | (←) move left | (→) move right | (Space) stop | (Tab) Cpu usage graph",
-    "(Shift + →) left col sort | (Shift + ←) right col sort | (Shift + ↓) dec sort | (Shift + ↑) inc sort",
-]; 
\ No newline at end of file
+pub const INFO_TEXT: [&str; 3] = [
+    "(Esc) quit | (↑) move up | (↓) move down | (←) move left | (→) move right | (Space) stop | (Enter) chart + details | (c) cpu info | (k) send signal",
+    "(Shift + →) left col sort | (Shift + ←) right col sort | (Shift + ↓) dec sort | (Shift + ↑) inc sort | (z) freeze order | (t) tree view | (+/-) expand/collapse | (I) cpu % mode",
+    "(Tab) Cpu usage graph | (m) memory graph | (p) pressure graph | (n) network graph | (b) disk I/O graph | (/) search (Tab: regex) | (f) filter | (o) columns",
+];
@@ -1,30 +1,93 @@
 use std::collections::VecDeque;
-use crate::models::MAX_LEN;
 use std::fmt::Display;
 
-pub fn push_bounded<T>(queue: &mut VecDeque<T>, item: T) {
+// jednostki rozmiarów - potęgi 1024 albo 1000
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Units {
+    Binary,
+    Decimal,
+}
+
+impl Units {
+    // podstawa kolejnych jednostek (K, M, G, T)
+    pub fn base(&self) -> f64 {
+        match self {
+            Units::Binary => 1024.0,
+            Units::Decimal => 1000.0,
+        }
+    }
+}
+
+// sposób liczenia procentu CPU procesu
+// Irix - 100% na każdy procesor logiczny (jak domyślnie w top i htop),
+// Solaris - udział w mocy wszystkich procesorów logicznych (cała maszyna to 100%)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CpuMode {
+    Irix,
+    Solaris,
+}
+
+impl CpuMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CpuMode::Irix => "irix",
+            CpuMode::Solaris => "solaris",
+        }
+    }
+
+    // przeliczenie zużycia procesu (100% = jeden procesor logiczny) przy `cpus` procesorach logicznych
+    pub fn normalize(&self, usage: f64, cpus: usize) -> f64 {
+        match self {
+            CpuMode::Irix => usage,
+            CpuMode::Solaris => usage / cpus.max(1) as f64,
+        }
+    }
+}
+
+pub fn push_bounded<T>(queue: &mut VecDeque<T>, item: T, limit: usize) {
     // implementacja kolejki pomiarów do wykresów
-    if queue.len() == MAX_LEN {
+    while queue.len() >= limit {
         queue.pop_front();
     }
     queue.push_back(item);
 }
 
-pub fn change_units(n: f64) -> String {
+pub fn change_units(n: f64, units: Units) -> String {
     // zamienia jednostki z bajtów na kilobajty itd.
+    let base = units.base();
+    let names = match units {
+        Units::Decimal => ["B", "kB", "MB", "GB", "TB"],
+        Units::Binary => ["B", "KB", "MB", "GB", "TB"],
+    };
     let mut num = n;
-    let mut i: i16 = 0;
-    while num > 1024.0 {
-        num /= 1024.0;
+    let mut i = 0;
+    while num > base && i < names.len() - 1 {
+        num /= base;
         i += 1;
     }
-    match i {
-        0 => format!("{:.1}B", num),
-        1 => format!("{:.1}KB", num),
-        2 => format!("{:.1}MB", num),
-        3 => format!("{:.1}GB", num),
-        _ => format!("{:.1}", num),
+    format!("{:.1}{}", num, names[i])
+}
+
+// częstotliwość podana w kHz, np. "2.40GHz" albo "800MHz"
+pub fn format_frequency(khz: u64) -> String {
+    if khz >= 1_000_000 {
+        format!("{:.2}GHz", khz as f64 / 1_000_000.0)
+    } else {
+        format!("{}MHz", khz / 1000)
+    }
+}
+
+// pasek z procentem do tabel, np. "███▌░░░░"
+pub fn percent_bar(percent: f64, width: usize) -> String {
+    const PARTS: [char; 8] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
+    let eighths = (percent.clamp(0.0, 100.0) / 100.0 * (width * 8) as f64).round() as usize;
+    let mut bar = "█".repeat(eighths / 8);
+    let rest = eighths % 8;
+    if rest > 0 {
+        bar.push(PARTS[rest - 1]);
     }
+    let filled = bar.chars().count();
+    bar + &"░".repeat(width - filled)
 }
 
 // funkcja do estetycznego pokazywanie danych
@@ -36,9 +99,41 @@ pub fn format_option<T: Display>(val: Option<T>) -> String {
 }
 
 // funkcja do estetycznego pokazywanie danych
-pub fn format_option_units(val: Option<f64>) -> String {
+pub fn format_option_units(val: Option<f64>, units: Units) -> String {
     match val {
-        Some(v) => change_units(v),
+        Some(v) => change_units(v, units),
         None => "--".to_string(),
     }
-} 
\ No newline at end of file
+} 
+// zamiana liczby sekund na czytelny czas trwania, np. "3d 04:05:06"
+pub fn format_duration(secs: u64) -> String {
+    let (days, rest) = (secs / 86_400, secs % 86_400);
+    let (h, m, s) = (rest / 3600, rest % 3600 / 60, rest % 60);
+    if days > 0 {
+        format!("{days}d {h:02}:{m:02}:{s:02}")
+    } else {
+        format!("{h:02}:{m:02}:{s:02}")
+    }
+}
+
+// zamiana znacznika czasu unix na datę w UTC (bez zewnętrznych bibliotek)
+pub fn format_timestamp(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let rest = secs % 86_400;
+    // algorytm "civil from days" (H. Hinnant)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02} UTC",
+        rest / 3600,
+        rest % 3600 / 60,
+        rest % 60
+    )
+}